@@ -23,6 +23,7 @@
 #include <future>
 #include <map>
 #include <memory>
+#include <optional>
 #include <string>
 #include <vector>
 #include <unordered_map>
@@ -34,6 +35,9 @@ namespace interpreter {
 class CycleDetector;
 struct DependencyGroup;  // From polyglot_dependency_analyzer.h
 }
+namespace runtime {
+class ScopedRustBlockCall;  // From rust_executor.h
+}
 }
 
 namespace naab {
@@ -481,6 +485,12 @@ public:
     // Phase 11.1: Flush captured output from polyglot executors
     void flushExecutorOutput(runtime::Executor* executor);
 
+    // Scope for a script's call into a block: for Rust blocks, pushes the
+    // call-site frame and routes FUNCTION arguments back to callFunction.
+    // Empty for other languages.
+    std::optional<runtime::ScopedRustBlockCall> makeRustBlockCall(
+        const BlockValue& block, const std::string& function_name, size_t line);
+
     // Phase 12: Polyglot header-aware injection
     std::string injectDeclarationsAfterHeaders(const std::string& declarations,
                                                 const std::string& code,
@@ -3,7 +3,10 @@
 // NAAb Manifest System
 // Loads and manages naab.toml project configuration
 
+#include <cstdint>
 #include <string>
+#include <utility>
+#include <variant>
 #include <vector>
 #include <unordered_map>
 #include <optional>
@@ -47,6 +50,16 @@ struct LanguageConfig {
 struct FeatureFlags {
     bool async_blocks;
     bool sandbox_mode;
+    std::unordered_map<std::string, bool> flags;  // Every boolean in [features]
+};
+
+// One setting of a Rust block library: TOML boolean, integer, float or string
+using RustSettingValue = std::variant<bool, int64_t, double, std::string>;
+
+// Load-time configuration for a Rust block library, from its
+// [rust."<lib path>"] table (the path as written in rust:// URIs)
+struct RustLibraryConfig {
+    std::vector<std::pair<std::string, RustSettingValue>> settings;  // Sorted by key
 };
 
 // Main manifest structure
@@ -57,6 +70,7 @@ struct Manifest {
     PolyglotConfig polyglot;
     std::unordered_map<std::string, LanguageConfig> languages;
     FeatureFlags features;
+    std::unordered_map<std::string, RustLibraryConfig> rust_libraries;
 
     // Validation
     bool validate() const;
@@ -1,10 +1,14 @@
 #pragma once
 
 #include "naab/language_registry.h"
+#include "naab/manifest.h"
 #include "naab/rust_ffi.h"
 #include "naab/output_buffer.h"
+#include "naab/stack_tracer.h"
 #include <atomic>
+#include <functional>
 #include <memory>
+#include <mutex>
 #include <string>
 #include <unordered_map>
 #include <vector>
@@ -16,6 +20,46 @@ namespace interpreter {
 
 namespace runtime {
 
+// Calls a NAAb function value with arguments (Interpreter::callFunction)
+using RustFunctionInvoker = std::function<std::shared_ptr<interpreter::Value>(
+    std::shared_ptr<interpreter::Value>, const std::vector<std::shared_ptr<interpreter::Value>>&)>;
+
+/**
+ * Makes FUNCTION values passed to Rust blocks on this thread callable
+ * (naab_rust_value_call) for the lifetime of the object. Nests.
+ */
+class ScopedRustFunctionInvoker {
+public:
+    explicit ScopedRustFunctionInvoker(RustFunctionInvoker invoker);
+    ~ScopedRustFunctionInvoker();
+
+    ScopedRustFunctionInvoker(const ScopedRustFunctionInvoker&) = delete;
+    ScopedRustFunctionInvoker& operator=(const ScopedRustFunctionInvoker&) = delete;
+
+private:
+    RustFunctionInvoker previous_;
+};
+
+/**
+ * Wraps a script's call into a Rust block: pushes a "naab" frame for the
+ * calling script line (reported by naab_rust_context_script_file/line and
+ * shown in traces) and makes FUNCTION arguments callable through
+ * invoker. Only used for Rust blocks, so other languages' traces keep
+ * their shape.
+ */
+class ScopedRustBlockCall {
+public:
+    ScopedRustBlockCall(const std::string& function_name, const std::string& script_file,
+                        size_t line, RustFunctionInvoker invoker);
+
+    ScopedRustBlockCall(const ScopedRustBlockCall&) = delete;
+    ScopedRustBlockCall& operator=(const ScopedRustBlockCall&) = delete;
+
+private:
+    error::ScopedStackFrame call_site_;
+    ScopedRustFunctionInvoker callbacks_;
+};
+
 /**
  * RustExecutor: Executes Rust blocks via FFI
  *
@@ -58,6 +102,71 @@ public:
         const std::vector<std::shared_ptr<interpreter::Value>>& args
     );
 
+    /**
+     * Set the configuration handed to a library's naab_rust_lib_init
+     * export when it is first loaded. If the library is already loaded,
+     * the new configuration is pushed to its naab_rust_lib_reconfigure
+     * export instead. Init runs once per process while any executor has
+     * the library loaded, so a library another executor loaded first
+     * keeps that executor's configuration. Loaded libraries stay loaded for the life of the
+     * executor, so one without that export cannot take new configuration.
+     *
+     * @param lib_path Path to .so file, as written in the block URI
+     * @param config Configuration value passed to the library
+     * @throws std::runtime_error if the library is loaded and has no
+     *         naab_rust_lib_reconfigure, or if that export rejects it
+     */
+    void setLibraryConfig(const std::string& lib_path,
+                          std::shared_ptr<interpreter::Value> config);
+
+    /**
+     * Apply the [rust."<lib>"] tables of naab.toml with setLibraryConfig.
+     * Each library gets a list of [key, value] pairs, sorted by key;
+     * integers that do not fit a NAAb int arrive as decimal strings.
+     *
+     * @throws std::runtime_error if a loaded library rejects its update
+     */
+    void configureLibraries(
+        const std::unordered_map<std::string, manifest::RustLibraryConfig>& libraries);
+
+    /**
+     * Set where reloaded library configuration comes from, normally the
+     * [rust."<lib>"] tables of a freshly read naab.toml. After
+     * requestConfigReload(), the next top-level block call on this
+     * executor (not one forwarded by naab_rust_host_call) applies it with
+     * configureLibraries first.
+     */
+    void setConfigSource(
+        std::function<std::unordered_map<std::string, manifest::RustLibraryConfig>()> source);
+
+    /**
+     * Request a configuration reload, applied before the next top-level
+     * block call of the executor that has a config source (the CLI's). Async-signal-
+     * safe, so a signal handler (the CLI's SIGUSR1) can call it.
+     */
+    static void requestConfigReload();
+
+    /**
+     * Run naab_rust_lib_shutdown for every Rust library, loaded by any
+     * executor, that has not been shut down yet. Call before _exit(),
+     * which skips destructors. Safe to call more than once. In builds with
+     * -DENABLE_RUST_LEAK_CHECK=ON the first call also reports FFI values
+     * that were never freed, so call it only at process exit.
+     */
+    static void shutdownAllLibraries();
+
+    /**
+     * Set the flags answered by naab_rust_feature_enabled, normally the
+     * [features] table of the project manifest. Replaces earlier flags.
+     */
+    static void setFeatureFlags(const std::unordered_map<std::string, bool>& flags);
+
+    /**
+     * Set the sandbox profile (--sandbox-level) reported to blocks by
+     * naab_rust_host_info.
+     */
+    static void setSandboxProfile(const std::string& profile);
+
 private:
     /**
      * Parse Rust block URI into library path and function name
@@ -74,10 +183,19 @@ private:
      *
      * @param lib_path Path to .so file
      * @return dlopen handle
-     * @throws std::runtime_error on dlopen failure
+     * @throws std::runtime_error on dlopen failure or if naab_rust_lib_init fails
      */
     void* loadLibrary(const std::string& lib_path);
 
+    /**
+     * Run the library's optional naab_rust_lib_init export with the
+     * configuration set for lib_path (VOID if none)
+     *
+     * @throws std::runtime_error if the configuration cannot be converted
+     *         or naab_rust_lib_init fails
+     */
+    void initLibrary(void* lib_handle, const std::string& lib_path);
+
     /**
      * Get function pointer from loaded library
      *
@@ -91,15 +209,47 @@ private:
     // Cache of loaded libraries: path -> dlopen handle
     std::unordered_map<std::string, void*> library_cache_;
 
+    // Load-time configuration per library path (for naab_rust_lib_init)
+    std::unordered_map<std::string, std::shared_ptr<interpreter::Value>> library_config_;
+
+    // Source of reloaded configuration (setConfigSource), and the reload request
+    std::function<std::unordered_map<std::string, manifest::RustLibraryConfig>()> config_source_;
+    static std::atomic<bool> config_reload_requested_;
+
+    // Apply config_source_ to every library, logging per-library failures
+    void reloadLibraryConfig();
+
     // Cache of resolved functions: "path::function" -> function pointer
     std::unordered_map<std::string, NaabRustBlockFn> function_cache_;
 
+    // naab_rust_dispatch export per loaded library (nullptr if not exported)
+    std::unordered_map<std::string, NaabRustDispatchFn> dispatch_cache_;
+
+    // naab_rust_validate_args export per loaded library (nullptr if not exported)
+    std::unordered_map<std::string, NaabRustValidateArgsFn> validator_cache_;
+
     // Output buffers for capturing stdout/stderr
     OutputBuffer stdout_buffer_;
     OutputBuffer stderr_buffer_;
 
-    // Phase 4.2.4: Rust error extraction
-    void extractRustError();
+    // Libraries loaded by any executor, keyed by dlopen handle. dlopen
+    // hands out one handle per library, so executors share entries; the
+    // first load runs naab_rust_lib_init.
+    struct LoadedLibrary {
+        NaabRustLibShutdownFn shutdown = nullptr;  // Pending naab_rust_lib_shutdown
+        int refs = 0;
+        std::vector<std::string> paths;  // URI paths its handles are issued under
+    };
+    static std::mutex library_mutex_;
+    static std::unordered_map<void*, LoadedLibrary> loaded_libraries_;
+
+    // Drop one executor's reference to a library. The last one runs its
+    // shutdown hook and forgets the handles it issued.
+    static void releaseLibrary(void* lib_handle);
+
+    // Phase 4.2.4: Take the pending Rust error report as a message suffix
+    // ("\n  Rust error: <message> (<file>:<line>)"), or "" if none
+    std::string extractRustError();
 
     // Thread-safe temp file counter for parallel execution
     static std::atomic<int> temp_file_counter_;
@@ -14,15 +14,71 @@ typedef enum {
     NAAB_RUST_TYPE_INT = 1,
     NAAB_RUST_TYPE_DOUBLE = 2,
     NAAB_RUST_TYPE_BOOL = 3,
-    NAAB_RUST_TYPE_STRING = 4
+    NAAB_RUST_TYPE_STRING = 4,
+    NAAB_RUST_TYPE_INT128 = 5,  // Two's complement, carried as two u64 halves
+    NAAB_RUST_TYPE_LIST = 6,
+    NAAB_RUST_TYPE_INT64 = 7,
+    NAAB_RUST_TYPE_NULL = 8,    // Explicit NAAb null, unlike VOID (no value)
+    NAAB_RUST_TYPE_HANDLE = 9,  // Opaque id of an object kept by the block library
+    NAAB_RUST_TYPE_FUNCTION = 10, // NAAb function or lambda, see naab_rust_value_call
+    NAAB_RUST_TYPE_UINT64 = 11    // Hashes, sizes
 } NaabRustValueType;
 
 // Opaque value handle
 typedef struct NaabRustValue NaabRustValue;
 
+// Opaque builder for chunked (streamed) string values
+typedef struct NaabRustStringBuilder NaabRustStringBuilder;
+
 // Block function signature
 typedef NaabRustValue* (*NaabRustBlockFn)(NaabRustValue** args, size_t arg_count);
 
+// Optional dispatch export "naab_rust_dispatch". When a library exports it,
+// the runtime resolves every block of that library through this single
+// entry point (one dlsym per library) instead of one dlsym per block.
+// name_hash is naab_rust_block_name_hash() of the block name.
+typedef NaabRustValue* (*NaabRustDispatchFn)(uint64_t name_hash, NaabRustValue** args, size_t arg_count);
+
+// Optional library init export "naab_rust_lib_init", called once right after
+// the first dlopen (later executors loading the same library share it) with
+// the load-time configuration for that library (VOID if none).
+// The CLI takes it from the library's [rust."<lib path>"] table in naab.toml,
+// passed as a LIST of [key, value] pairs sorted by key.
+// Return 0 on success; any other value aborts loading the library.
+typedef int (*NaabRustLibInitFn)(const NaabRustValue* config);
+
+// Optional export "naab_rust_lib_shutdown", called exactly once before the
+// library is unloaded or the CLI exits, so buffered writes can be flushed.
+typedef void (*NaabRustLibShutdownFn)();
+
+// Optional export "naab_rust_lib_reconfigure", called with updated
+// configuration while the library stays loaded. Return 0 to accept it;
+// any other value rejects it and the previous configuration stays in effect.
+typedef int (*NaabRustLibReconfigureFn)(const NaabRustValue* config);
+
+// Optional export "naab_rust_validate_args", called before every block of
+// the library runs, with the same (borrowed) arguments the block will get.
+// Return 0 to let the call proceed; any other value rejects it without
+// running the block, with the reason taken from naab_rust_report_error.
+typedef int (*NaabRustValidateArgsFn)(const char* block_name, NaabRustValue* const* args, size_t arg_count);
+
+// Optional export "naab_rust_required_runtime" returning a semver range
+// (e.g. ">=0.5.0,<1.0.0"; partial versions such as ">=1.4, <2" mean
+// ">=1.4.0, <2.0.0"). The runtime refuses to load the library, before
+// naab_rust_lib_init runs, when its own version does not satisfy it. A
+// malformed range is reported on stderr and does not block loading.
+typedef const char* (*NaabRustRequiredRuntimeFn)();
+
+// Runtime environment reported by naab_rust_host_info. All strings are
+// owned by the runtime and stay valid for the life of the process.
+typedef struct {
+    const char* version;          // Same as naab_rust_runtime_version()
+    const char* platform;         // "linux", "android" (incl. Termux), "macos", "windows", "freebsd", "unknown"
+    const char* arch;             // "x86_64", "aarch64", "arm", "x86", "riscv64", "unknown"
+    const char* sandbox_profile;  // "restricted", "standard", "elevated", "unrestricted", "" if unset
+    const char* temp_dir;         // Temp directory the runtime itself uses
+} NaabRustHostInfo;
+
 // Phase 4.2.4: Error metadata for stack tracing
 typedef struct {
     char* message;
@@ -30,27 +86,182 @@ typedef struct {
     uint32_t line;
 } NaabRustError;
 
-// Value creation functions
+// Value creation functions. All of them (and naab_rust_string_finish)
+// return NULL when the runtime cannot allocate the value; callers must
+// check instead of passing the NULL on.
 NaabRustValue* naab_rust_value_create_int(int value);
 NaabRustValue* naab_rust_value_create_double(double value);
+NaabRustValue* naab_rust_value_create_float(float value);
 NaabRustValue* naab_rust_value_create_bool(bool value);
 NaabRustValue* naab_rust_value_create_string(const char* value);
 NaabRustValue* naab_rust_value_create_void();
+NaabRustValue* naab_rust_value_create_null();
+NaabRustValue* naab_rust_value_create_int128(uint64_t high, uint64_t low);
+NaabRustValue* naab_rust_value_create_int64(int64_t value);
+NaabRustValue* naab_rust_value_create_uint64(uint64_t value);
+
+// Opaque handle to an object that stays inside the block library (parser,
+// DB connection). The runtime only carries the id and the type tag (copied;
+// NULL means ""): scripts see {"__rust_handle__": "<id>", "type": "<tag>",
+// "library": "<lib path>"} and can pass it to later calls. The handle is
+// tagged with the library whose block returned it (wherever it was created,
+// worker threads included), and the runtime refuses to pass it to a block
+// of any other library. Library and type are checked against the runtime's
+// record of handles blocks returned, not against the script-visible fields,
+// so edited copies are refused. The record is dropped when the library is
+// shut down or unloaded. The library owns the object and must drop it
+// itself, e.g. from a "close" block.
+NaabRustValue* naab_rust_value_create_handle(uint64_t id, const char* type_tag);
+
+// List creation: the list takes ownership of every element (the array itself
+// is only read), so free the list, never its elements. On failure, if any
+// element is NULL, or if the list would nest deeper than NAAB_RUST_MAX_DEPTH
+// levels (a scalar is one level), the non-NULL elements are freed and NULL
+// is returned.
+NaabRustValue* naab_rust_value_create_list(NaabRustValue** elements, size_t count);
+
+// Chunked string creation for very large strings: begin a builder, append
+// chunks (no embedded NUL bytes), then finish it into a STRING value. The
+// buffer is handed over without a final copy. finish and abort consume the
+// builder. begin returns NULL and append returns false on allocation failure;
+// begin also returns NULL when size_hint alone exceeds the sandbox limit below.
+// append also returns false once the call is interrupted (Ctrl-C) or when the
+// string would outgrow the active sandbox's memory limit; the builder stays
+// valid either way, so the block can abort it and return.
+NaabRustStringBuilder* naab_rust_string_begin(size_t size_hint);
+bool naab_rust_string_append_chunk(NaabRustStringBuilder* builder, const char* chunk, size_t len);
+NaabRustValue* naab_rust_string_finish(NaabRustStringBuilder* builder);
+void naab_rust_string_abort(NaabRustStringBuilder* builder);
 
 // Value access functions
+// get_string returns a borrowed, NUL-terminated pointer into the value. It
+// stays valid and unchanged until the value is freed (values are immutable)
+// and must not be freed by the caller. Non-STRING values yield a static "".
 int naab_rust_value_get_int(const NaabRustValue* value);
 double naab_rust_value_get_double(const NaabRustValue* value);
 bool naab_rust_value_get_bool(const NaabRustValue* value);
 const char* naab_rust_value_get_string(const NaabRustValue* value);
 NaabRustValueType naab_rust_value_get_type(const NaabRustValue* value);
 
+// f32 access. The runtime stores every float as an f64: create_float makes a
+// DOUBLE holding the exact widened value, so it reads back unchanged.
+// get_float narrows a DOUBLE with round-to-nearest-even; magnitudes from
+// FLT_MAX + 2^103 (half an ulp past FLT_MAX) become +/-infinity, smaller
+// ones above FLT_MAX become +/-FLT_MAX, and NaN stays NaN. 0.0f for
+// non-DOUBLE values.
+float naab_rust_value_get_float(const NaabRustValue* value);
+
+// 64-bit access: the value of an INT64, or of an INT widened; 0 for any
+// other type. NAAb ints are 32-bit, so the runtime converts INT64 to an int
+// when it fits and otherwise to its exact decimal string (never truncated).
+int64_t naab_rust_value_get_int64(const NaabRustValue* value);
+
+// Unsigned 64-bit access: the value of a UINT64, or of a non-negative INT or
+// INT64; 0 for anything else (negative numbers are not wrapped). Like INT64,
+// a UINT64 reaches scripts as an int when it fits, else as its decimal string.
+uint64_t naab_rust_value_get_uint64(const NaabRustValue* value);
+
+// Handle access: writes the id and a borrowed type tag (valid until the value
+// is freed) and returns true for HANDLE values; false for any other type.
+bool naab_rust_value_get_handle(const NaabRustValue* value, uint64_t* id, const char** type_tag);
+
+// Call a FUNCTION value (a NAAb function or lambda passed as an argument),
+// e.g. to implement map/filter. Only valid on the thread running the block
+// call that received it. Arguments are borrowed; returns a new value owned
+// by the caller, or NULL with the error reported via naab_rust_report_error.
+NaabRustValue* naab_rust_value_call(const NaabRustValue* function, NaabRustValue* const* args, size_t arg_count);
+
+// List access: length is 0 for non-LIST values. get_element returns a
+// borrowed element owned by the list (do not free it), or NULL when the
+// index is out of range or the value is not a LIST.
+size_t naab_rust_value_get_length(const NaabRustValue* value);
+const NaabRustValue* naab_rust_value_get_element(const NaabRustValue* value, size_t index);
+
+// 128-bit access: writes both halves and returns true for INT128 values,
+// returns false (leaving the outputs untouched) for any other type. The
+// runtime converts INT128 to an int when it fits, else to a decimal string.
+bool naab_rust_value_get_int128(const NaabRustValue* value, uint64_t* high, uint64_t* low);
+
 // Memory management
+// free releases a value and, for lists, every element it owns (deep free).
+// clone returns an independent deep copy, or NULL on allocation failure.
 void naab_rust_value_free(NaabRustValue* value);
+NaabRustValue* naab_rust_value_clone(const NaabRustValue* value);
+
+// Deepest nesting the runtime converts between host and FFI values; deeper
+// values, and host lists that contain themselves, fail the conversion.
+#define NAAB_RUST_MAX_DEPTH 128
 
 // Phase 4.2.4: Error handling
+// Blocks report failures (including panics) with naab_rust_report_error;
+// block_name and file may be NULL. Reports are kept per thread: the most
+// recent one made on the calling thread during a block call is attached to
+// that call's trace if it fails. Reports from other threads are only logged.
+void naab_rust_report_error(const char* block_name, const char* message,
+                            const char* file, uint32_t line);
 NaabRustError* naab_rust_get_last_error();
 void naab_rust_error_free(NaabRustError* error);
 
+// Runtime version (semver string) for block-side compatibility checks
+const char* naab_rust_runtime_version();
+
+// Fill *info with the runtime version, platform, CPU architecture, sandbox
+// profile and temp directory, so blocks can adapt at run time.
+void naab_rust_host_info(NaabRustHostInfo* info);
+
+// Runtime-managed feature flag (the [features] table of naab.toml).
+// Unknown names and NULL are reported as disabled.
+bool naab_rust_feature_enabled(const char* name);
+
+// CPUs this process may use: the affinity mask, capped by any cgroup CPU
+// quota (rounded up). Always >= 1. Size block-side thread pools from this.
+uint32_t naab_rust_host_cpu_quota();
+
+// Call-scoped context, valid on the thread that invoked the block for the
+// duration of the call: a unique call ID (0 outside a call) and the NAAb
+// script location that triggered it ("" and 0 when unknown).
+uint64_t naab_rust_context_call_id();
+const char* naab_rust_context_script_file();
+uint32_t naab_rust_context_script_line();
+
+// Call another Rust block from inside a block (decorators, wrappers).
+// block is a full "rust://lib.so::name" URI or a bare name in the calling
+// block's library. Arguments are borrowed - the caller still owns and
+// frees them. Returns a new value owned by the caller, or NULL with the
+// error reported through naab_rust_report_error.
+NaabRustValue* naab_rust_host_call(const char* block, NaabRustValue* const* args, size_t arg_count);
+
+// Static registration for builds that cannot dlopen block libraries (iOS,
+// locked-down hosts): a statically linked library calls this, typically from
+// a constructor, for each block. rust://<library>::<name> then resolves to
+// fn without loading anything; no lib_init/lib_shutdown hooks run for it.
+// Re-registering a name replaces it. Returns false if any argument is NULL
+// or library/name contain ':', which block URIs cannot express.
+bool naab_rust_register_static_block(const char* library, const char* name, NaabRustBlockFn fn);
+
+// Block name hash used by naab_rust_dispatch: 64-bit FNV-1a over the
+// UTF-8 bytes of the name (offset 0xcbf29ce484222325, prime 0x100000001b3)
+uint64_t naab_rust_block_name_hash(const char* name);
+
+// Cooperative interrupt handling: becomes true when the user hits Ctrl-C
+// while a Rust block is running. Long-running blocks should poll this,
+// flush their state and return; the runtime re-raises SIGINT afterwards.
+// A second Ctrl-C ends the process at once, without naab_rust_lib_shutdown.
+bool naab_rust_interrupted();
+
+// Sandbox path checks, so blocks can validate path arguments against the
+// host allowlists before touching the filesystem. Block calls run under the
+// configured sandbox (--sandbox-level); both return true when no sandbox is
+// active on the calling thread (e.g. a block's own worker thread), false
+// for a null path.
+bool naab_rust_sandbox_can_read(const char* path);
+bool naab_rust_sandbox_can_write(const char* path);
+
+// Environment variable read routed through the sandbox (SYS_ENV), so blocks
+// need not bypass it with their own getenv. Returns a STRING value (caller
+// frees), a VOID value if the variable is unset, or NULL if denied.
+NaabRustValue* naab_rust_host_getenv(const char* name);
+
 #ifdef __cplusplus
 }
 #endif
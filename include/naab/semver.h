@@ -33,6 +33,9 @@ struct SemanticVersion {
     // Parse version string (e.g., "1.2.3-alpha.1+build.123")
     static SemanticVersion parse(const std::string& version_str);
 
+    // Parse a version inside a range: also accepts "1" and "1.4" (missing parts are 0)
+    static SemanticVersion parseRequirement(const std::string& version_str);
+
     // Convert to string representation
     std::string toString() const;
     std::string toStringWithBuild() const;
@@ -48,7 +51,8 @@ struct SemanticVersion {
     // Compatibility checks
     bool isCompatibleWith(const SemanticVersion& required) const;
 
-    // Range satisfaction (e.g., ">=1.0.0,<2.0.0", "^1.2.3", "~1.2.3")
+    // Range satisfaction (e.g., ">=1.0.0,<2.0.0", "^1.2.3", "~1.2.3", ">=1.4, <2").
+    // Throws VersionParseException if the range is malformed.
     bool satisfiesRange(const std::string& range) const;
 
 private:
@@ -42,6 +42,7 @@
 #include <sstream>
 #include <string>
 #include <vector>
+#include <csignal>
 #include <cstdlib>
 #include <unistd.h>  // _exit()
 #include <filesystem>
@@ -529,6 +530,7 @@ int main(int argc, char** argv) {
 
         // Set default config for SandboxManager
         naab::security::SandboxManager::instance().setDefaultConfig(security_config);
+        naab::runtime::RustExecutor::setSandboxProfile(sandbox_level);
 
         // Configure Python import blocking based on sandbox level
         // NOTE: Temporarily disabled while using pure C API (PythonCExecutor)
@@ -543,6 +545,29 @@ int main(int argc, char** argv) {
         auto manifest = naab::manifest::ManifestLoader::findAndLoad(".");
         if (manifest.has_value()) {
             // Manifest loaded - configuration will be applied by interpreter
+            naab::runtime::RustExecutor::setFeatureFlags(manifest->features.flags);
+            #ifdef HAVE_RUST
+            if (auto* rust = dynamic_cast<naab::runtime::RustExecutor*>(
+                    naab::runtime::LanguageRegistry::instance().getExecutor("rust"))) {
+                rust->configureLibraries(manifest->rust_libraries);
+
+                // Long-running scripts pick up edited [rust."<lib>"] tables on SIGUSR1.
+                // Not SIGHUP: closing the terminal must still end the script.
+                if (!manifest->rust_libraries.empty()) {
+                    rust->setConfigSource([] {
+                        auto reloaded = naab::manifest::ManifestLoader::findAndLoad(".");
+                        if (!reloaded) {
+                            throw std::runtime_error("could not reload naab.toml: " +
+                                                     naab::manifest::ManifestLoader::getLastError());
+                        }
+                        return reloaded->rust_libraries;
+                    });
+                    std::signal(SIGUSR1, [](int) {
+                        naab::runtime::RustExecutor::requestConfigReload();
+                    });
+                }
+            }
+            #endif
             if (verbose) {
                 fmt::print("[Manifest] Using project: {} v{}\n",
                            manifest->package.name, manifest->package.version);
@@ -793,6 +818,9 @@ int main(int argc, char** argv) {
             // Python thread states trigger bionic CFI crashes during static
             // destruction on Android. _exit() is safe: the OS cleans up all
             // process resources, and we've already flushed all output.
+            // Rust libraries get their shutdown hooks first, since _exit()
+            // skips the executor destructors.
+            naab::runtime::RustExecutor::shutdownAllLibraries();
             fflush(stdout);
             fflush(stderr);
             _exit(0);
@@ -800,11 +828,13 @@ int main(int argc, char** argv) {
         } catch (const naab::interpreter::NaabError& e) {
             // NaabError has full stack trace - print it
             fmt::print("{}\n", e.formatError());
+            naab::runtime::RustExecutor::shutdownAllLibraries();
             fflush(stdout);
             fflush(stderr);
             _exit(1);
         } catch (const std::exception& e) {
             fmt::print("Error: {}\n", e.what());
+            naab::runtime::RustExecutor::shutdownAllLibraries();
             fflush(stdout);
             fflush(stderr);
             _exit(1);
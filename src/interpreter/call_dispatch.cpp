@@ -13,10 +13,13 @@
 #include "naab/struct_registry.h"
 #include "naab/error_helpers.h"
 #include "naab/js_executor_adapter.h"
+#include "naab/rust_executor.h"
+#include "naab/stack_tracer.h"
 #include <fmt/core.h>
 #include <iostream>
 #include <sstream>
 #include <climits>
+#include <optional>
 
 namespace naab {
 namespace interpreter {
@@ -2264,6 +2267,7 @@ void Interpreter::visit(ast::CallExpr& node) {
                     : block->member_path;
 
                 LOG_DEBUG("[INFO] Calling function: {}\n", function_to_call);
+                auto rust_call = makeRustBlockCall(*block, function_to_call, node.getLocation().line);
                 result_ = executor->callFunction(function_to_call, args);
                 flushExecutorOutput(executor);  // Phase 11.1: Flush captured output
 
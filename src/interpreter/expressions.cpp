@@ -11,6 +11,7 @@
 #include "naab/error_helpers.h"
 #include "naab/logger.h"
 #include "naab/struct_registry.h"
+#include "naab/rust_executor.h"
 #include <fmt/core.h>
 #include <sstream>
 #include <climits>
@@ -773,6 +774,8 @@ void Interpreter::visit(ast::BinaryExpr& node) {
                     if (!executor) {
                         throw std::runtime_error("No executor for block in pipeline");
                     }
+                    auto rust_call = makeRustBlockCall(**block, (*block)->metadata.block_id,
+                                                       node.getLocation().line);
                     result_ = executor->callFunction((*block)->metadata.block_id, args);
                     flushExecutorOutput(executor);  // Phase 11.1: Flush captured output
 
@@ -819,6 +822,8 @@ void Interpreter::visit(ast::BinaryExpr& node) {
                     if (!executor) {
                         throw std::runtime_error("No executor for block in pipeline");
                     }
+                    auto rust_call = makeRustBlockCall(**block, (*block)->metadata.block_id,
+                                                       node.getLocation().line);
                     result_ = executor->callFunction((*block)->metadata.block_id, args);
                     flushExecutorOutput(executor);  // Phase 11.1: Flush captured output
 
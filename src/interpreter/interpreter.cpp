@@ -22,6 +22,7 @@
 #include "naab/resource_limits.h"  // Enterprise security: Resource limits
 #include "naab/source_mapper.h"  // Phase 12: Polyglot error mapping
 #include "naab/json_result_parser.h"  // Phase 12: JSON sovereign pipe
+#include "naab/rust_executor.h"
 #include <fmt/core.h>
 #include <iostream>
 #include <sstream>
@@ -756,6 +757,19 @@ void Interpreter::flushExecutorOutput(runtime::Executor* executor) {
     }
 }
 
+// Rust blocks see the calling script line and may call back into NAAb
+std::optional<runtime::ScopedRustBlockCall> Interpreter::makeRustBlockCall(
+    const BlockValue& block, const std::string& function_name, size_t line) {
+    if (block.metadata.language != "rust") {
+        return std::nullopt;
+    }
+    return std::optional<runtime::ScopedRustBlockCall>(
+        std::in_place, function_name, current_file_, line,
+        [this](std::shared_ptr<Value> fn, const std::vector<std::shared_ptr<Value>>& fn_args) {
+            return callFunction(std::move(fn), fn_args);
+        });
+}
+
 // ============================================================================
 // Phase 4.1: Stack Trace Helpers
 // ============================================================================
@@ -100,12 +100,47 @@ std::optional<Manifest> ManifestLoader::load(const std::string& file_path) {
         if (auto features = config["features"]) {
             manifest.features.async_blocks = features["async_blocks"].value_or(false);
             manifest.features.sandbox_mode = features["sandbox_mode"].value_or(true);
+            if (auto table = features.as_table()) {
+                for (auto& [key, value] : *table) {
+                    if (auto enabled = value.value<bool>()) {
+                        manifest.features.flags[std::string(key)] = *enabled;
+                    }
+                }
+            }
         } else {
             // Default features
             manifest.features.async_blocks = false;
             manifest.features.sandbox_mode = true;
         }
 
+        // Parse [rust."<lib path>"] sections (Rust block library configuration)
+        if (auto rust = config["rust"].as_table()) {
+            for (auto& [lib_path, node] : *rust) {
+                auto settings = node.as_table();
+                if (!settings) {
+                    LOG_WARN("[Manifest] Ignoring rust.\"{}\": expected a table of settings, "
+                             "e.g. [rust.\"{}\"]\n", std::string(lib_path), std::string(lib_path));
+                    continue;
+                }
+                RustLibraryConfig& lib_config = manifest.rust_libraries[std::string(lib_path)];
+                for (auto& [key, value] : *settings) {
+                    if (auto b = value.as_boolean()) {
+                        lib_config.settings.emplace_back(std::string(key), b->get());
+                    } else if (auto i = value.as_integer()) {
+                        lib_config.settings.emplace_back(std::string(key), i->get());
+                    } else if (auto d = value.as_floating_point()) {
+                        lib_config.settings.emplace_back(std::string(key), d->get());
+                    } else if (auto str = value.as_string()) {
+                        lib_config.settings.emplace_back(std::string(key), str->get());
+                    } else {
+                        LOG_WARN("[Manifest] Ignoring rust.\"{}\".{}: only booleans, numbers "
+                                 "and strings are passed to Rust libraries\n",
+                                 std::string(lib_path), std::string(key));
+                    }
+                }
+            }
+        }
+
         // Validate manifest
         if (!manifest.validate()) {
             last_error_ = manifest.getError();
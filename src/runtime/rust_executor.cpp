@@ -4,9 +4,16 @@
 #include "naab/interpreter.h"  // Phase 2.3: MUST be first for Value definition
 #include "naab/rust_executor.h"
 #include "naab/rust_ffi.h"
+#include "naab/config.h"
+#include "naab/sandbox.h"
+#include "naab/semver.h"
 #include "naab/stack_tracer.h"  // Phase 4.2.4: Cross-language stack traces
 #include "naab/subprocess_helpers.h"  // For execute_subprocess_with_pipes
 #include <dlfcn.h>
+#include <algorithm>
+#include <climits>
+#include <csignal>
+#include <cstring>
 #include <fmt/core.h>
 #include <stdexcept>
 #include <regex>
@@ -14,6 +21,7 @@
 #include <sstream>
 #include <filesystem>
 #include <thread>
+#include <utility>
 
 namespace naab {
 namespace runtime {
@@ -21,15 +29,231 @@ namespace runtime {
 // Initialize static temp file counter for thread-safe unique file names
 std::atomic<int> RustExecutor::temp_file_counter_(0);
 
+std::atomic<bool> RustExecutor::config_reload_requested_(false);
+
+std::mutex RustExecutor::library_mutex_;
+std::unordered_map<void*, RustExecutor::LoadedLibrary> RustExecutor::loaded_libraries_;
+
 // Forward declarations for FFI conversion helpers
 std::shared_ptr<interpreter::Value> ffiToValue(NaabRustValue* ffi_val);
 NaabRustValue* valueToFfi(const std::shared_ptr<interpreter::Value>& val);
+void setRustInterrupted(bool interrupted);
+size_t reportRustValueLeaks();
+void setRustCallContext(uint64_t call_id, const std::string& script_file, uint32_t script_line);
+void setRustFeatureFlags(const std::unordered_map<std::string, bool>& flags);
+void setRustSandboxProfile(const std::string& profile);
+RustFunctionInvoker setRustFunctionInvoker(RustFunctionInvoker invoker);
+std::string setRustCurrentLibrary(std::string library);
+void forgetRustLibraryHandles(const std::string& library);
+
+namespace {
+
+// Routes Ctrl-C to naab_rust_interrupted() while any block call is running
+// so the block can flush and return instead of being killed mid-write.
+// The disposition is process-wide and calls on different threads overlap,
+// so the handler is installed by the first active call and restored by the
+// last one, under a mutex. A second Ctrl-C falls back to the default
+// action and ends the process at once. If a block returned after an
+// interrupt, SIGINT is re-raised once the previous handler is back; when
+// that re-raise is about to end the process, libraries get
+// naab_rust_lib_shutdown first. An inherited SIG_IGN (nohup, background
+// jobs) is left alone: Ctrl-C is not meant to reach the process at all.
+class ScopedRustInterrupt {
+public:
+    ScopedRustInterrupt() {
+        std::lock_guard<std::mutex> lock(mutex());
+        if (active_calls()++ > 0) {
+            return;  // Nested or concurrent call: keep the pending interrupt
+        }
+
+        struct sigaction sa;
+        std::memset(&sa, 0, sizeof(sa));
+        sa.sa_handler = handleInterrupt;
+        sa.sa_flags = SA_RESTART;
+        sigemptyset(&sa.sa_mask);
+        installed() = sigaction(SIGINT, nullptr, &previous()) == 0 &&
+                      previous().sa_handler != SIG_IGN &&
+                      sigaction(SIGINT, &sa, nullptr) == 0;
+        setRustInterrupted(false);
+    }
+
+    ~ScopedRustInterrupt() {
+        bool reraise = false;
+        bool fatal = false;
+        {
+            std::lock_guard<std::mutex> lock(mutex());
+            if (--active_calls() > 0 || !installed()) {
+                return;
+            }
+            sigaction(SIGINT, &previous(), nullptr);
+            installed() = false;
+            reraise = naab_rust_interrupted();
+            fatal = previous().sa_handler == SIG_DFL;
+            setRustInterrupted(false);
+        }
+        if (reraise) {
+            if (fatal) {
+                // The re-raise ends the process: flush libraries first
+                RustExecutor::shutdownAllLibraries();
+            }
+            raise(SIGINT);
+        }
+    }
+
+    ScopedRustInterrupt(const ScopedRustInterrupt&) = delete;
+    ScopedRustInterrupt& operator=(const ScopedRustInterrupt&) = delete;
+
+private:
+    static void handleInterrupt(int sig) {
+        if (naab_rust_interrupted()) {
+            // Block ignored the first request - stop waiting for it. The
+            // shutdown hooks take locks and may need the interrupted block's
+            // state, so they are not run from here.
+            signal(sig, SIG_DFL);
+            raise(sig);
+            return;
+        }
+        setRustInterrupted(true);
+    }
+
+    // Function-local statics, shared by every call on every thread
+    static std::mutex& mutex() {
+        static std::mutex m;
+        return m;
+    }
+    static int& active_calls() {
+        static int count = 0;
+        return count;
+    }
+    static bool& installed() {
+        static bool value = false;
+        return value;
+    }
+    static struct sigaction& previous() {
+        static struct sigaction action;
+        return action;
+    }
+};
+
+// Publishes the call ID and triggering script location through the
+// naab_rust_context_* exports for one block call, restoring the outer
+// context afterwards so nested calls stay correct.
+class ScopedRustCallContext {
+public:
+    explicit ScopedRustCallContext(uint64_t call_id)
+        : prev_call_id_(naab_rust_context_call_id()),
+          prev_file_(naab_rust_context_script_file()),
+          prev_line_(naab_rust_context_script_line()) {
+        // Innermost NAAb frame is the script line that called this block
+        std::string file;
+        uint32_t line = 0;
+        auto trace = error::StackTracer::getTrace();
+        for (auto it = trace.rbegin(); it != trace.rend(); ++it) {
+            if (it->language == "naab") {
+                file = it->filename;
+                line = static_cast<uint32_t>(it->line_number);
+                break;
+            }
+        }
+        setRustCallContext(call_id, file, line);
+    }
+
+    ~ScopedRustCallContext() {
+        setRustCallContext(prev_call_id_, prev_file_, prev_line_);
+    }
+
+    ScopedRustCallContext(const ScopedRustCallContext&) = delete;
+    ScopedRustCallContext& operator=(const ScopedRustCallContext&) = delete;
+
+private:
+    uint64_t prev_call_id_;
+    std::string prev_file_;
+    uint32_t prev_line_;
+};
+
+// Blocks registered with naab_rust_register_static_block, keyed by
+// "library::name". Function-local statics: registration may run from
+// constructors before this file's globals are initialized.
+std::mutex& staticBlocksMutex() {
+    static std::mutex mutex;
+    return mutex;
+}
+
+std::unordered_map<std::string, NaabRustBlockFn>& staticBlocks() {
+    static std::unordered_map<std::string, NaabRustBlockFn> blocks;
+    return blocks;
+}
+
+NaabRustBlockFn findStaticBlock(const std::string& key) {
+    std::lock_guard<std::mutex> lock(staticBlocksMutex());
+    auto it = staticBlocks().find(key);
+    return it != staticBlocks().end() ? it->second : nullptr;
+}
+
+// Source of naab_rust_context_call_id() values; 0 means "no call"
+std::atomic<uint64_t> next_call_id{1};
+
+// Executor and library of the block call running on this thread, so
+// naab_rust_host_call can route forwarded calls back through it and
+// handles are only passed back to the library that created them
+thread_local RustExecutor* current_executor = nullptr;
+thread_local const std::string* current_lib_path = nullptr;
+
+class ScopedCurrentExecutor {
+public:
+    ScopedCurrentExecutor(RustExecutor* executor, const std::string* lib_path)
+        : prev_executor_(current_executor), prev_lib_path_(current_lib_path),
+          prev_library_(setRustCurrentLibrary(*lib_path)) {
+        current_executor = executor;
+        current_lib_path = lib_path;
+    }
+
+    ~ScopedCurrentExecutor() {
+        current_executor = prev_executor_;
+        current_lib_path = prev_lib_path_;
+        setRustCurrentLibrary(std::move(prev_library_));
+    }
+
+    ScopedCurrentExecutor(const ScopedCurrentExecutor&) = delete;
+    ScopedCurrentExecutor& operator=(const ScopedCurrentExecutor&) = delete;
+
+private:
+    RustExecutor* prev_executor_;
+    const std::string* prev_lib_path_;
+    std::string prev_library_;  // Library that bridge-side handles are tagged with
+};
+
+// One naab.toml setting as the value a library's config list carries
+std::shared_ptr<interpreter::Value> settingToValue(const manifest::RustSettingValue& setting) {
+    if (const auto* i = std::get_if<int64_t>(&setting)) {
+        if (*i >= INT_MIN && *i <= INT_MAX) {
+            return std::make_shared<interpreter::Value>(static_cast<int>(*i));
+        }
+        return std::make_shared<interpreter::Value>(std::to_string(*i));  // Like INT64 results
+    }
+    if (const auto* d = std::get_if<double>(&setting)) {
+        return std::make_shared<interpreter::Value>(*d);
+    }
+    if (const auto* b = std::get_if<bool>(&setting)) {
+        return std::make_shared<interpreter::Value>(*b);
+    }
+    return std::make_shared<interpreter::Value>(std::get<std::string>(setting));
+}
+
+} // namespace
 
 RustExecutor::RustExecutor() {
     // RustExecutor initialized (silent)
 }
 
 RustExecutor::~RustExecutor() {
+    // Give libraries their flush callback before anything is unloaded
+    for (const auto& [path, handle] : library_cache_) {
+        if (handle) {
+            releaseLibrary(handle);
+        }
+    }
+
     // Clean up loaded libraries
     for (const auto& [path, handle] : library_cache_) {
         if (handle) {
@@ -39,6 +263,78 @@ RustExecutor::~RustExecutor() {
     }
 }
 
+void RustExecutor::releaseLibrary(void* lib_handle) {
+    LoadedLibrary library;
+    {
+        std::lock_guard<std::mutex> lock(library_mutex_);
+        auto it = loaded_libraries_.find(lib_handle);
+        if (it == loaded_libraries_.end() || --it->second.refs > 0) {
+            return;
+        }
+        library = std::move(it->second);
+        loaded_libraries_.erase(it);
+    }
+    if (library.shutdown) {
+        library.shutdown();
+    }
+    for (const auto& path : library.paths) {
+        forgetRustLibraryHandles(path);
+    }
+}
+
+ScopedRustFunctionInvoker::ScopedRustFunctionInvoker(RustFunctionInvoker invoker)
+    : previous_(setRustFunctionInvoker(std::move(invoker))) {}
+
+ScopedRustFunctionInvoker::~ScopedRustFunctionInvoker() {
+    setRustFunctionInvoker(std::move(previous_));
+}
+
+ScopedRustBlockCall::ScopedRustBlockCall(const std::string& function_name,
+                                         const std::string& script_file,
+                                         size_t line, RustFunctionInvoker invoker)
+    : call_site_("naab", function_name, script_file, line),
+      callbacks_(std::move(invoker)) {}
+
+void RustExecutor::setFeatureFlags(const std::unordered_map<std::string, bool>& flags) {
+    setRustFeatureFlags(flags);
+}
+
+void RustExecutor::setSandboxProfile(const std::string& profile) {
+    setRustSandboxProfile(profile);
+}
+
+void RustExecutor::shutdownAllLibraries() {
+    // Entries stay: executors still hold their references
+    std::vector<NaabRustLibShutdownFn> hooks;
+    std::vector<std::string> paths;
+    {
+        std::lock_guard<std::mutex> lock(library_mutex_);
+        for (auto& [handle, library] : loaded_libraries_) {
+            if (library.shutdown) {
+                hooks.push_back(std::exchange(library.shutdown, nullptr));
+            }
+            paths.insert(paths.end(), library.paths.begin(), library.paths.end());
+        }
+    }
+    for (auto hook : hooks) {
+        hook();
+    }
+    for (const auto& path : paths) {
+        forgetRustLibraryHandles(path);
+    }
+
+    // Process exit: whatever is still live leaked. Libraries are still
+    // loaded, so creation backtraces resolve. Other executors' calls may
+    // be in flight before this point, so no executor reports on its own.
+    static std::atomic<bool> reported{false};
+    if (!reported.exchange(true)) {
+        size_t leaked = reportRustValueLeaks();
+        if (leaked > 0) {
+            fmt::print(stderr, "[WARN] {} Rust FFI value(s) were never freed\n", leaked);
+        }
+    }
+}
+
 // Executor interface: execute code (store for later call)
 bool RustExecutor::execute(const std::string& code) {
     // For inline Rust code, compile and execute immediately
@@ -354,38 +650,94 @@ std::shared_ptr<interpreter::Value> RustExecutor::executeBlock(
     std::string lib_path, func_name;
     parseRustURI(code, lib_path, func_name);
 
+    // Pick up configuration reloaded since the last call (e.g. on SIGUSR1).
+    // Not from naab_rust_host_call: an outer block of the library may be running.
+    if (config_source_ && !current_executor && config_reload_requested_.exchange(false)) {
+        reloadLibraryConfig();
+    }
+
     // Phase 4.2.4: Push stack frame for cross-language tracing
     error::ScopedStackFrame stack_frame("rust", func_name, "<rust>", 0);
 
     // Check function cache first
     std::string cache_key = lib_path + "::" + func_name;
     NaabRustBlockFn func = nullptr;
+    NaabRustDispatchFn dispatch = nullptr;
 
     auto cache_it = function_cache_.find(cache_key);
-    if (cache_it != function_cache_.end()) {
+    if ((func = findStaticBlock(cache_key))) {
+        // Statically linked block: no dlopen. Not cached, so re-registering
+        // the name takes effect on the next call.
+        fmt::print("[INFO] Using statically registered Rust function: {}\n", cache_key);
+    } else if (cache_it != function_cache_.end()) {
         func = cache_it->second;
         fmt::print("[INFO] Using cached Rust function: {}\n", cache_key);
     } else {
-        // Load library and get function
+        // Load library; libraries exporting naab_rust_dispatch need no per-block dlsym
         void* lib_handle = loadLibrary(lib_path);
-        func = getFunction(lib_handle, func_name);
+        dispatch = dispatch_cache_[lib_path];
+
+        if (!dispatch) {
+            func = getFunction(lib_handle, func_name);
 
-        // Cache for future calls
-        function_cache_[cache_key] = func;
-        fmt::print("[INFO] Cached Rust function: {}\n", cache_key);
+            // Cache for future calls
+            function_cache_[cache_key] = func;
+            fmt::print("[INFO] Cached Rust function: {}\n", cache_key);
+        }
     }
 
+    // Scope covers argument and result conversion (handle library checks)
+    ScopedCurrentExecutor current(this, &lib_path);
+
     // Convert C++ arguments to FFI
     std::vector<NaabRustValue*> ffi_args;
     ffi_args.reserve(args.size());
     for (const auto& arg : args) {
-        ffi_args.push_back(valueToFfi(arg));
+        NaabRustValue* ffi_arg = nullptr;
+        try {
+            ffi_arg = valueToFfi(arg);
+        } catch (const std::exception& e) {
+            for (auto* converted : ffi_args) {
+                naab_rust_value_free(converted);
+            }
+            throw std::runtime_error(fmt::format(
+                "Invalid argument for Rust function '{}': {}", func_name, e.what()));
+        }
+        if (!ffi_arg) {
+            for (auto* converted : ffi_args) {
+                naab_rust_value_free(converted);
+            }
+            throw std::runtime_error(fmt::format(
+                "Out of memory converting arguments for Rust function '{}'", func_name));
+        }
+        ffi_args.push_back(ffi_arg);
     }
 
     // Call Rust function
     NaabRustValue* ffi_result = nullptr;
     try {
-        ffi_result = func(ffi_args.data(), ffi_args.size());
+        ScopedRustInterrupt interrupt_scope;
+        // Host services the block calls (getenv, sandbox checks, string
+        // limits) answer for the configured sandbox, as for inline blocks
+        security::ScopedSandbox sandbox_scope(security::SandboxManager::instance().getDefaultConfig());
+        ScopedRustCallContext call_context(next_call_id.fetch_add(1));
+        naab_rust_error_free(naab_rust_get_last_error());  // Stale report from an earlier call
+
+        auto validator_it = validator_cache_.find(lib_path);
+        if (validator_it != validator_cache_.end() && validator_it->second &&
+            validator_it->second(func_name.c_str(), ffi_args.data(), ffi_args.size()) != 0) {
+            std::string rust_error = extractRustError();
+            throw std::runtime_error(fmt::format(
+                "Rust library '{}' rejected arguments for '{}'{}\n{}",
+                lib_path, func_name, rust_error, error::StackTracer::formatTrace()));
+        }
+
+        if (dispatch) {
+            ffi_result = dispatch(naab_rust_block_name_hash(func_name.c_str()),
+                                  ffi_args.data(), ffi_args.size());
+        } else {
+            ffi_result = func(ffi_args.data(), ffi_args.size());
+        }
     } catch (...) {
         // Clean up arguments on exception
         for (auto* ffi_arg : ffi_args) {
@@ -401,21 +753,99 @@ std::shared_ptr<interpreter::Value> RustExecutor::executeBlock(
 
     // Convert result back to C++ Value
     if (!ffi_result) {
-        // Phase 4.2.4: Extract Rust error and add to unified trace
-        extractRustError();
+        // Phase 4.2.4: Report the Rust error location with the unified trace
+        std::string rust_error = extractRustError();
 
         // Re-throw with enriched stack trace
         throw std::runtime_error(fmt::format(
-            "Rust function '{}' returned null (error occurred)\n{}",
-            func_name, error::StackTracer::formatTrace()));
+            "Rust function '{}' returned null (error occurred){}\n{}",
+            func_name, rust_error, error::StackTracer::formatTrace()));
     }
 
-    auto result = ffiToValue(ffi_result);
+    std::shared_ptr<interpreter::Value> result;
+    try {
+        result = ffiToValue(ffi_result);
+    } catch (...) {
+        naab_rust_value_free(ffi_result);
+        throw;
+    }
     naab_rust_value_free(ffi_result);
 
     return result;
 }
 
+void RustExecutor::setLibraryConfig(const std::string& lib_path,
+                                    std::shared_ptr<interpreter::Value> config) {
+    auto lib_it = library_cache_.find(lib_path);
+    if (lib_it != library_cache_.end()) {
+        dlerror();
+        auto reconfigure = reinterpret_cast<NaabRustLibReconfigureFn>(
+            dlsym(lib_it->second, "naab_rust_lib_reconfigure"));
+        if (!reconfigure) {
+            throw std::runtime_error(fmt::format(
+                "Rust library '{}' is already loaded and has no naab_rust_lib_reconfigure; "
+                "restart to apply the new configuration", lib_path));
+        }
+
+        NaabRustValue* ffi_config = valueToFfi(config);
+        if (!ffi_config) {
+            throw std::runtime_error(fmt::format(
+                "Out of memory converting configuration for Rust library '{}'", lib_path));
+        }
+        int status = reconfigure(ffi_config);
+        naab_rust_value_free(ffi_config);
+
+        if (status != 0) {
+            throw std::runtime_error(fmt::format(
+                "Rust library '{}' rejected new configuration "
+                "(naab_rust_lib_reconfigure returned {}){}", lib_path, status, extractRustError()));
+        }
+    }
+    library_config_[lib_path] = std::move(config);
+}
+
+void RustExecutor::configureLibraries(
+    const std::unordered_map<std::string, manifest::RustLibraryConfig>& libraries) {
+    for (const auto& [lib_path, lib_config] : libraries) {
+        std::vector<std::shared_ptr<interpreter::Value>> pairs;
+        pairs.reserve(lib_config.settings.size());
+        for (const auto& [key, setting] : lib_config.settings) {
+            pairs.push_back(std::make_shared<interpreter::Value>(
+                std::vector<std::shared_ptr<interpreter::Value>>{
+                    std::make_shared<interpreter::Value>(key), settingToValue(setting)}));
+        }
+        setLibraryConfig(lib_path, std::make_shared<interpreter::Value>(std::move(pairs)));
+    }
+}
+
+void RustExecutor::setConfigSource(
+    std::function<std::unordered_map<std::string, manifest::RustLibraryConfig>()> source) {
+    config_source_ = std::move(source);
+}
+
+void RustExecutor::requestConfigReload() {
+    config_reload_requested_.store(true);
+}
+
+void RustExecutor::reloadLibraryConfig() {
+    std::unordered_map<std::string, manifest::RustLibraryConfig> libraries;
+    try {
+        libraries = config_source_();
+    } catch (const std::exception& e) {
+        fmt::print(stderr, "[WARN] Rust library configuration reload failed: {}\n", e.what());
+        return;
+    }
+
+    // One library refusing its update must not keep the others on old settings
+    for (auto& entry : libraries) {
+        try {
+            configureLibraries({std::move(entry)});
+        } catch (const std::exception& e) {
+            fmt::print(stderr, "[WARN] {}\n", e.what());
+        }
+    }
+}
+
 void RustExecutor::parseRustURI(const std::string& uri,
                                 std::string& lib_path,
                                 std::string& func_name) {
@@ -455,6 +885,63 @@ void* RustExecutor::loadLibrary(const std::string& lib_path) {
         );
     }
 
+    // Refuse libraries built for an incompatible runtime
+    dlerror();
+    auto required_runtime = reinterpret_cast<NaabRustRequiredRuntimeFn>(
+        dlsym(handle, "naab_rust_required_runtime"));
+    if (required_runtime) {
+        const char* range = required_runtime();
+        if (range && *range) {
+            // A requirement that cannot be checked does not block loading
+            bool compatible = true;
+            try {
+                compatible = versioning::SemanticVersion::parse(NAAB_VERSION_STRING)
+                                 .satisfiesRange(range);
+            } catch (const versioning::VersionParseException& e) {
+                fmt::print(stderr, "[WARN] Rust library {} has a malformed runtime requirement "
+                           "'{}' ({}); loading it anyway\n", lib_path, range, e.what());
+            }
+            if (!compatible) {
+                dlclose(handle);
+                throw std::runtime_error(fmt::format(
+                    "Rust library '{}' requires NAAb {}, but running v{}",
+                    lib_path, range, NAAB_VERSION_STRING));
+            }
+        }
+    }
+
+    // Executors share the dlopen handle: only its first load runs
+    // naab_rust_lib_init and arms naab_rust_lib_shutdown. The lock keeps a
+    // concurrent load from using the library before init has finished.
+    {
+        std::lock_guard<std::mutex> lock(library_mutex_);
+        auto library_it = loaded_libraries_.find(handle);
+        if (library_it == loaded_libraries_.end()) {
+            try {
+                initLibrary(handle, lib_path);
+            } catch (...) {
+                dlclose(handle);
+                throw;
+            }
+            library_it = loaded_libraries_.emplace(handle, LoadedLibrary{}).first;
+            library_it->second.shutdown = reinterpret_cast<NaabRustLibShutdownFn>(
+                dlsym(handle, "naab_rust_lib_shutdown"));
+        }
+        auto& library = library_it->second;
+        library.refs++;
+        if (std::find(library.paths.begin(), library.paths.end(), lib_path) == library.paths.end()) {
+            library.paths.push_back(lib_path);
+        }
+    }
+
+    // Single-symbol dispatch, if the library provides it
+    dispatch_cache_[lib_path] = reinterpret_cast<NaabRustDispatchFn>(
+        dlsym(handle, "naab_rust_dispatch"));
+
+    // Library-wide argument policy, if the library provides one
+    validator_cache_[lib_path] = reinterpret_cast<NaabRustValidateArgsFn>(
+        dlsym(handle, "naab_rust_validate_args"));
+
     // Cache the handle
     library_cache_[lib_path] = handle;
     fmt::print("[INFO] Loaded Rust library: {}\n", lib_path);
@@ -462,6 +949,39 @@ void* RustExecutor::loadLibrary(const std::string& lib_path) {
     return handle;
 }
 
+void RustExecutor::initLibrary(void* lib_handle, const std::string& lib_path) {
+    dlerror();
+    auto lib_init = reinterpret_cast<NaabRustLibInitFn>(dlsym(lib_handle, "naab_rust_lib_init"));
+    if (lib_init) {
+        std::shared_ptr<interpreter::Value> config;
+        auto config_it = library_config_.find(lib_path);
+        if (config_it != library_config_.end()) {
+            config = config_it->second;
+        }
+
+        NaabRustValue* ffi_config = nullptr;
+        try {
+            ffi_config = valueToFfi(config);
+        } catch (const std::exception& e) {
+            throw std::runtime_error(fmt::format(
+                "Invalid configuration for Rust library '{}': {}", lib_path, e.what()));
+        }
+        if (!ffi_config) {
+            throw std::runtime_error(fmt::format(
+                "Out of memory converting configuration for Rust library '{}'", lib_path));
+        }
+
+        int status = lib_init(ffi_config);
+        naab_rust_value_free(ffi_config);
+
+        if (status != 0) {
+            throw std::runtime_error(fmt::format(
+                "Rust library '{}' failed to initialize (naab_rust_lib_init returned {}){}",
+                lib_path, status, extractRustError()));
+        }
+    }
+}
+
 NaabRustBlockFn RustExecutor::getFunction(void* lib_handle, const std::string& func_name) {
     if (!lib_handle) {
         throw std::runtime_error("Cannot get function from null library handle");
@@ -498,34 +1018,25 @@ NaabRustBlockFn RustExecutor::getFunction(void* lib_handle, const std::string& f
 // Phase 4.2.4: Rust Error Extraction
 // ============================================================================
 
-void RustExecutor::extractRustError() {
-    try {
-        // Get last error from Rust FFI
-        NaabRustError* rust_error = naab_rust_get_last_error();
-
-        if (!rust_error) {
-            // No error information available
-            return;
-        }
-
-        // Extract error details
-        std::string error_message = rust_error->message ? rust_error->message : "Unknown Rust error";
-        std::string error_file = rust_error->file ? rust_error->file : "<unknown>";
-        uint32_t error_line = rust_error->line;
+std::string RustExecutor::extractRustError() {
+    // Get last error from Rust FFI
+    NaabRustError* rust_error = naab_rust_get_last_error();
 
-        // Add Rust frame to stack trace
-        error::StackFrame rust_frame("rust", error_message, error_file, error_line);
-        error::StackTracer::pushFrame(rust_frame);
+    if (!rust_error) {
+        // No error information available
+        return "";
+    }
 
-        fmt::print("[TRACE] Rust frame: {} ({}:{})\n",
-            error_message, error_file, error_line);
+    // Extract error details. Not pushed as a stack frame: nothing would pop
+    // it, and the script may catch the exception and carry on.
+    std::string error_message = rust_error->message ? rust_error->message : "Unknown Rust error";
+    std::string error_file = rust_error->file ? rust_error->file : "<unknown>";
+    uint32_t error_line = rust_error->line;
 
-        // Free error structure
-        naab_rust_error_free(rust_error);
+    // Free error structure
+    naab_rust_error_free(rust_error);
 
-    } catch (const std::exception& ex) {
-        fmt::print("[WARN] Failed to extract Rust error: {}\n", ex.what());
-    }
+    return fmt::format("\n  Rust error: {} ({}:{})", error_message, error_file, error_line);
 }
 
 std::string RustExecutor::getCapturedOutput() {
@@ -539,3 +1050,60 @@ std::string RustExecutor::getCapturedOutput() {
 
 } // namespace runtime
 } // namespace naab
+
+// ============================================================================
+// Forwarded Block Calls
+// ============================================================================
+
+extern "C" NaabRustValue* naab_rust_host_call(const char* block,
+                                              NaabRustValue* const* args,
+                                              size_t arg_count) {
+    using naab::runtime::current_executor;
+    using naab::runtime::current_lib_path;
+
+    if (!block || (!args && arg_count > 0)) {
+        naab_rust_report_error(block, "naab_rust_host_call: invalid arguments", nullptr, 0);
+        return nullptr;
+    }
+    if (!current_executor) {
+        naab_rust_report_error(block, "naab_rust_host_call: no Rust block call in progress", nullptr, 0);
+        return nullptr;
+    }
+
+    // Bare names refer to blocks in the calling block's library
+    std::string uri = block;
+    if (uri.rfind("rust://", 0) != 0) {
+        uri = "rust://" + *current_lib_path + "::" + uri;
+    }
+
+    try {
+        // Arguments are borrowed: converted to host values, never freed here
+        std::vector<std::shared_ptr<naab::interpreter::Value>> values;
+        values.reserve(arg_count);
+        for (size_t i = 0; i < arg_count; ++i) {
+            values.push_back(naab::runtime::ffiToValue(args[i]));
+        }
+
+        auto result = current_executor->executeBlock(uri, values);
+        return naab::runtime::valueToFfi(result);
+    } catch (const std::exception& e) {
+        naab_rust_report_error(block, e.what(), nullptr, 0);
+        return nullptr;
+    }
+}
+
+// ============================================================================
+// Static Block Registration
+// ============================================================================
+
+extern "C" bool naab_rust_register_static_block(const char* library,
+                                                const char* name,
+                                                NaabRustBlockFn fn) {
+    if (!library || !name || !fn || std::strchr(library, ':') || std::strchr(name, ':')) {
+        return false;
+    }
+
+    std::lock_guard<std::mutex> lock(naab::runtime::staticBlocksMutex());
+    naab::runtime::staticBlocks()[std::string(library) + "::" + name] = fn;
+    return true;
+}
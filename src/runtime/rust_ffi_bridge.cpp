@@ -2,21 +2,156 @@
 // C-compatible interface for Rust block interoperability
 
 #include "naab/rust_ffi.h"
+#include "naab/config.h"
+#include "naab/paths.h"
 #include "naab/interpreter.h"
+#include "naab/logger.h"
+#include "naab/sandbox.h"
+#include <algorithm>
+#include <atomic>
+#include <cctype>
+#include <cerrno>
+#include <cfloat>
+#include <climits>
+#include <cmath>
+#include <cstdio>
+#include <cstdlib>
 #include <cstring>
+#include <functional>
+#include <limits>
 #include <memory>
+#include <mutex>
+#include <new>
+#include <stdexcept>
 #include <string>
+#include <thread>
+#include <unordered_map>
+#include <vector>
+
+#ifdef __linux__
+#include <sched.h>
+#endif
+
+#ifdef NAAB_RUST_TRACE_FFI
+#include <sstream>
+#endif
+
+// Live-value registry: in Debug builds (use-after-free and double-free
+// checks) and in any build with -DENABLE_RUST_LEAK_CHECK=ON. It locks a
+// global mutex on every value access, so other builds leave it out.
+#if defined(NAAB_RUST_LEAK_CHECK) || defined(NAAB_RUST_CHECK_VALUES)
+#define NAAB_RUST_TRACK_VALUES 1
+#endif
+
+#if defined(NAAB_RUST_TRACK_VALUES) && defined(__GLIBC__)
+#include <execinfo.h>
+#endif
 
 using namespace naab::interpreter;
 
+// FFI call tracing (-DENABLE_RUST_FFI_TRACE=ON): logs every value
+// create/get/free with its pointer and thread so ownership bugs between
+// Rust blocks and the runtime can be followed without a debugger.
+#ifdef NAAB_RUST_TRACE_FFI
+static void traceRustFfi(const char* op, const void* value) {
+    std::ostringstream tid;
+    tid << std::this_thread::get_id();
+    fprintf(stderr, "[rust-ffi] thread=%s %s %p\n", tid.str().c_str(), op, value);
+}
+#define RUST_FFI_TRACE(op, value) traceRustFfi(op, value)
+#else
+#define RUST_FFI_TRACE(op, value) ((void)0)
+#endif
+
+// Live-value registry. Every value created through this bridge stays
+// registered until freed. Debug builds abort with a clear message when a
+// get_* or free receives a pointer that is not live (use-after-free,
+// double free, foreign pointer) instead of corrupting the heap. With
+// -DENABLE_RUST_LEAK_CHECK=ON, values a block forgot to free are reported
+// with their creation backtraces at shutdown.
+#ifdef NAAB_RUST_TRACK_VALUES
+struct LiveRustValue {
+    const char* op;
+    std::vector<void*> frames;  // Creation backtrace (leak check only)
+};
+
+static std::mutex live_values_mutex;
+
+// Never destroyed: values may still be freed during static destruction
+static std::unordered_map<const NaabRustValue*, LiveRustValue>& liveValues() {
+    static auto* values = new std::unordered_map<const NaabRustValue*, LiveRustValue>();
+    return *values;
+}
+
+[[noreturn]] static void abortOnDeadValue(const char* op, const NaabRustValue* value) {
+    fprintf(stderr,
+        "[rust-ffi] FATAL: %s on value %p that is not live "
+        "(already freed, double free, or not created by naab_rust_value_create_*)\n",
+        op, static_cast<const void*>(value));
+    abort();
+}
+#endif
+
+static void trackCreated(const char* op, const NaabRustValue* value) {
+    RUST_FFI_TRACE(op, value);
+#ifdef NAAB_RUST_TRACK_VALUES
+    LiveRustValue entry{op, {}};
+#if defined(NAAB_RUST_LEAK_CHECK) && defined(__GLIBC__)
+    entry.frames.resize(32);
+    entry.frames.resize(backtrace(entry.frames.data(), static_cast<int>(entry.frames.size())));
+#endif
+    std::lock_guard<std::mutex> lock(live_values_mutex);
+    liveValues()[value] = std::move(entry);
+#endif
+}
+
+static void trackAccess(const char* op, const NaabRustValue* value) {
+    RUST_FFI_TRACE(op, value);
+#ifdef NAAB_RUST_TRACK_VALUES
+    if (!value) return;
+    std::lock_guard<std::mutex> lock(live_values_mutex);
+    if (liveValues().count(value) == 0) {
+        abortOnDeadValue(op, value);
+    }
+#endif
+}
+
+static void trackFreed(const NaabRustValue* value) {
+    RUST_FFI_TRACE("free", value);
+#ifdef NAAB_RUST_TRACK_VALUES
+    if (!value) return;
+    std::lock_guard<std::mutex> lock(live_values_mutex);
+    if (liveValues().erase(value) == 0) {
+        abortOnDeadValue("free", value);
+    }
+#endif
+}
+
 // Internal value representation
 struct NaabRustValue {
     NaabRustValueType type;
     union {
         int int_val;
+        int64_t int64_val;
+        uint64_t uint64_val;
         double double_val;
         bool bool_val;
         char* string_val;  // Owned string (must be freed)
+        struct {
+            uint64_t high;
+            uint64_t low;
+        } int128_val;
+        struct {
+            uint64_t id;
+            char* type_tag;  // Owned string (must be freed)
+            char* library;   // Owned string: library that created it, "" if none
+        } handle_val;
+        std::shared_ptr<Value>* function_val;  // Owned reference to the host function
+        struct {
+            NaabRustValue** items;  // Owned elements (freed with the list)
+            size_t count;
+            size_t depth;  // Levels including this one; bounds free/clone recursion
+        } list_val;
     } data;
 };
 
@@ -25,44 +160,275 @@ struct NaabRustValue {
 // ============================================================================
 
 NaabRustValue* naab_rust_value_create_int(int value) {
-    auto* v = new NaabRustValue();
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
     v->type = NAAB_RUST_TYPE_INT;
     v->data.int_val = value;
+    trackCreated("create_int", v);
     return v;
 }
 
 NaabRustValue* naab_rust_value_create_double(double value) {
-    auto* v = new NaabRustValue();
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
     v->type = NAAB_RUST_TYPE_DOUBLE;
     v->data.double_val = value;
+    trackCreated("create_double", v);
     return v;
 }
 
+NaabRustValue* naab_rust_value_create_float(float value) {
+    return naab_rust_value_create_double(static_cast<double>(value));  // Exact
+}
+
 NaabRustValue* naab_rust_value_create_bool(bool value) {
-    auto* v = new NaabRustValue();
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
     v->type = NAAB_RUST_TYPE_BOOL;
     v->data.bool_val = value;
+    trackCreated("create_bool", v);
     return v;
 }
 
 NaabRustValue* naab_rust_value_create_string(const char* value) {
-    auto* v = new NaabRustValue();
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
     v->type = NAAB_RUST_TYPE_STRING;
     v->data.string_val = strdup(value);  // Allocate owned copy
+    if (!v->data.string_val) {
+        delete v;
+        return nullptr;
+    }
+    trackCreated("create_string", v);
     return v;
 }
 
 NaabRustValue* naab_rust_value_create_void() {
-    auto* v = new NaabRustValue();
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
     v->type = NAAB_RUST_TYPE_VOID;
+    trackCreated("create_void", v);
+    return v;
+}
+
+NaabRustValue* naab_rust_value_create_uint64(uint64_t value) {
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
+    v->type = NAAB_RUST_TYPE_UINT64;
+    v->data.uint64_val = value;
+    trackCreated("create_uint64", v);
+    return v;
+}
+
+NaabRustValue* naab_rust_value_create_null() {
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
+    v->type = NAAB_RUST_TYPE_NULL;
+    trackCreated("create_null", v);
+    return v;
+}
+
+NaabRustValue* naab_rust_value_create_int128(uint64_t high, uint64_t low) {
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
+    v->type = NAAB_RUST_TYPE_INT128;
+    v->data.int128_val.high = high;
+    v->data.int128_val.low = low;
+    trackCreated("create_int128", v);
+    return v;
+}
+
+NaabRustValue* naab_rust_value_create_int64(int64_t value) {
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
+    v->type = NAAB_RUST_TYPE_INT64;
+    v->data.int64_val = value;
+    trackCreated("create_int64", v);
+    return v;
+}
+
+// Library of the Rust block call running on this thread ("" outside calls)
+static thread_local std::string current_library;
+
+static NaabRustValue* createHandleValue(uint64_t id, const char* type_tag, const char* library) {
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
+    v->type = NAAB_RUST_TYPE_HANDLE;
+    v->data.handle_val.id = id;
+    v->data.handle_val.type_tag = strdup(type_tag ? type_tag : "");
+    v->data.handle_val.library = strdup(library);
+    if (!v->data.handle_val.type_tag || !v->data.handle_val.library) {
+        free(v->data.handle_val.type_tag);
+        free(v->data.handle_val.library);
+        delete v;
+        return nullptr;
+    }
+    trackCreated("create_handle", v);
+    return v;
+}
+
+NaabRustValue* naab_rust_value_create_handle(uint64_t id, const char* type_tag) {
+    return createHandleValue(id, type_tag, current_library.c_str());
+}
+
+// FUNCTION values come only from the host (valueToFfi); blocks cannot create them
+static NaabRustValue* createFunctionValue(const std::shared_ptr<Value>& function) {
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) return nullptr;
+    v->type = NAAB_RUST_TYPE_FUNCTION;
+    v->data.function_val = new (std::nothrow) std::shared_ptr<Value>(function);
+    if (!v->data.function_val) {
+        delete v;
+        return nullptr;
+    }
+    trackCreated("create_function", v);
+    return v;
+}
+
+// Nesting levels of a value: 1 for scalars, stored at creation for lists
+static size_t valueDepth(const NaabRustValue* value) {
+    return value->type == NAAB_RUST_TYPE_LIST ? value->data.list_val.depth : 1;
+}
+
+NaabRustValue* naab_rust_value_create_list(NaabRustValue** elements, size_t count) {
+    auto freeElements = [&]() {
+        for (size_t i = 0; i < count; ++i) {
+            naab_rust_value_free(elements[i]);
+        }
+    };
+
+    if (count > 0 && !elements) {
+        return nullptr;
+    }
+    size_t depth = 1;
+    for (size_t i = 0; i < count; ++i) {
+        if (!elements[i]) {
+            freeElements();
+            return nullptr;
+        }
+        depth = std::max(depth, valueDepth(elements[i]) + 1);
+    }
+    if (depth > NAAB_RUST_MAX_DEPTH) {
+        freeElements();
+        return nullptr;
+    }
+
+    auto* v = new (std::nothrow) NaabRustValue();
+    NaabRustValue** items = nullptr;
+    if (v && count > 0) {
+        items = new (std::nothrow) NaabRustValue*[count];
+    }
+    if (!v || (count > 0 && !items)) {
+        delete v;
+        freeElements();
+        return nullptr;
+    }
+
+    std::copy(elements, elements + count, items);
+    v->type = NAAB_RUST_TYPE_LIST;
+    v->data.list_val.items = items;
+    v->data.list_val.count = count;
+    v->data.list_val.depth = depth;
+    trackCreated("create_list", v);
     return v;
 }
 
+// ============================================================================
+// Chunked String Creation
+// ============================================================================
+
+struct NaabRustStringBuilder {
+    char* data;  // malloc'd, becomes the value's string_val on finish
+    size_t len;
+    size_t cap;
+};
+
+// Largest string the active sandbox lets a block build; 0 means no limit
+static size_t stringSizeLimit() {
+    auto* sandbox = naab::security::ScopedSandbox::getCurrent();
+    if (!sandbox || sandbox->getConfig().hasCapability(naab::security::Capability::RES_UNLIMITED_MEM)) {
+        return 0;
+    }
+    return sandbox->getConfig().max_memory_mb * 1024 * 1024;
+}
+
+NaabRustStringBuilder* naab_rust_string_begin(size_t size_hint) {
+    size_t cap = size_hint + 1;  // Room for the terminator
+    if (cap == 0) return nullptr;  // size_t overflow
+
+    // The hint is allocated up front, so it is held to the same limit as append
+    size_t limit = stringSizeLimit();
+    if (limit > 0 && cap > limit) return nullptr;
+
+    char* data = static_cast<char*>(malloc(cap));
+    if (!data) return nullptr;
+
+    auto* builder = new (std::nothrow) NaabRustStringBuilder();
+    if (!builder) {
+        free(data);
+        return nullptr;
+    }
+    builder->data = data;
+    builder->len = 0;
+    builder->cap = cap;
+    return builder;
+}
+
+bool naab_rust_string_append_chunk(NaabRustStringBuilder* builder, const char* chunk, size_t len) {
+    if (!builder || (!chunk && len > 0)) return false;
+
+    // Cancellation point for blocks streaming out huge strings
+    if (naab_rust_interrupted()) return false;
+
+    size_t needed = builder->len + len + 1;
+    if (needed < builder->len) return false;  // size_t overflow
+
+    size_t limit = stringSizeLimit();
+    if (limit > 0 && needed > limit) return false;
+
+    if (needed > builder->cap) {
+        size_t new_cap = builder->cap * 2 > needed ? builder->cap * 2 : needed;
+        char* grown = static_cast<char*>(realloc(builder->data, new_cap));
+        if (!grown) return false;
+        builder->data = grown;
+        builder->cap = new_cap;
+    }
+
+    if (len > 0) {
+        memcpy(builder->data + builder->len, chunk, len);
+        builder->len += len;
+    }
+    return true;
+}
+
+NaabRustValue* naab_rust_string_finish(NaabRustStringBuilder* builder) {
+    if (!builder) return nullptr;
+
+    builder->data[builder->len] = '\0';
+    auto* v = new (std::nothrow) NaabRustValue();
+    if (!v) {
+        naab_rust_string_abort(builder);
+        return nullptr;
+    }
+    v->type = NAAB_RUST_TYPE_STRING;
+    v->data.string_val = builder->data;  // Ownership moves to the value
+    delete builder;
+    trackCreated("string_finish", v);
+    return v;
+}
+
+void naab_rust_string_abort(NaabRustStringBuilder* builder) {
+    if (!builder) return;
+    free(builder->data);
+    delete builder;
+}
+
 // ============================================================================
 // Value Access Functions (Tasks 3.1.11-3.1.16)
 // ============================================================================
 
 int naab_rust_value_get_int(const NaabRustValue* value) {
+    trackAccess("get_int", value);
     if (!value || value->type != NAAB_RUST_TYPE_INT) {
         return 0;
     }
@@ -70,13 +436,27 @@ int naab_rust_value_get_int(const NaabRustValue* value) {
 }
 
 double naab_rust_value_get_double(const NaabRustValue* value) {
+    trackAccess("get_double", value);
     if (!value || value->type != NAAB_RUST_TYPE_DOUBLE) {
         return 0.0;
     }
     return value->data.double_val;
 }
 
+float naab_rust_value_get_float(const NaabRustValue* value) {
+    double d = naab_rust_value_get_double(value);
+    if (d > FLT_MAX || d < -FLT_MAX) {
+        // Out of range for a plain cast (UB), so round by hand: FLT_MAX plus
+        // half an ulp (2^103) is the tie, which goes to infinity (FLT_MAX is odd)
+        static const double overflow = static_cast<double>(FLT_MAX) + std::ldexp(1.0, 103);
+        float magnitude = std::fabs(d) < overflow ? FLT_MAX : std::numeric_limits<float>::infinity();
+        return d > 0 ? magnitude : -magnitude;
+    }
+    return static_cast<float>(d);
+}
+
 bool naab_rust_value_get_bool(const NaabRustValue* value) {
+    trackAccess("get_bool", value);
     if (!value || value->type != NAAB_RUST_TYPE_BOOL) {
         return false;
     }
@@ -84,13 +464,82 @@ bool naab_rust_value_get_bool(const NaabRustValue* value) {
 }
 
 const char* naab_rust_value_get_string(const NaabRustValue* value) {
+    trackAccess("get_string", value);
     if (!value || value->type != NAAB_RUST_TYPE_STRING) {
         return "";
     }
     return value->data.string_val;
 }
 
+bool naab_rust_value_get_int128(const NaabRustValue* value, uint64_t* high, uint64_t* low) {
+    trackAccess("get_int128", value);
+    if (!value || value->type != NAAB_RUST_TYPE_INT128 || !high || !low) {
+        return false;
+    }
+    *high = value->data.int128_val.high;
+    *low = value->data.int128_val.low;
+    return true;
+}
+
+int64_t naab_rust_value_get_int64(const NaabRustValue* value) {
+    trackAccess("get_int64", value);
+    if (!value) {
+        return 0;
+    }
+    if (value->type == NAAB_RUST_TYPE_INT64) {
+        return value->data.int64_val;
+    }
+    if (value->type == NAAB_RUST_TYPE_INT) {
+        return value->data.int_val;
+    }
+    return 0;
+}
+
+uint64_t naab_rust_value_get_uint64(const NaabRustValue* value) {
+    trackAccess("get_uint64", value);
+    if (!value) {
+        return 0;
+    }
+    switch (value->type) {
+        case NAAB_RUST_TYPE_UINT64:
+            return value->data.uint64_val;
+        case NAAB_RUST_TYPE_INT64:
+            return value->data.int64_val >= 0 ? static_cast<uint64_t>(value->data.int64_val) : 0;
+        case NAAB_RUST_TYPE_INT:
+            return value->data.int_val >= 0 ? static_cast<uint64_t>(value->data.int_val) : 0;
+        default:
+            return 0;
+    }
+}
+
+bool naab_rust_value_get_handle(const NaabRustValue* value, uint64_t* id, const char** type_tag) {
+    trackAccess("get_handle", value);
+    if (!value || value->type != NAAB_RUST_TYPE_HANDLE || !id || !type_tag) {
+        return false;
+    }
+    *id = value->data.handle_val.id;
+    *type_tag = value->data.handle_val.type_tag;
+    return true;
+}
+
+size_t naab_rust_value_get_length(const NaabRustValue* value) {
+    trackAccess("get_length", value);
+    if (!value || value->type != NAAB_RUST_TYPE_LIST) {
+        return 0;
+    }
+    return value->data.list_val.count;
+}
+
+const NaabRustValue* naab_rust_value_get_element(const NaabRustValue* value, size_t index) {
+    trackAccess("get_element", value);
+    if (!value || value->type != NAAB_RUST_TYPE_LIST || index >= value->data.list_val.count) {
+        return nullptr;
+    }
+    return value->data.list_val.items[index];
+}
+
 NaabRustValueType naab_rust_value_get_type(const NaabRustValue* value) {
+    trackAccess("get_type", value);
     if (!value) {
         return NAAB_RUST_TYPE_VOID;
     }
@@ -102,16 +551,352 @@ NaabRustValueType naab_rust_value_get_type(const NaabRustValue* value) {
 // ============================================================================
 
 void naab_rust_value_free(NaabRustValue* value) {
+    trackFreed(value);
     if (!value) return;
 
     // Free owned string if present
     if (value->type == NAAB_RUST_TYPE_STRING && value->data.string_val) {
         free(value->data.string_val);
     }
+    if (value->type == NAAB_RUST_TYPE_HANDLE) {
+        free(value->data.handle_val.type_tag);
+        free(value->data.handle_val.library);
+    }
+    if (value->type == NAAB_RUST_TYPE_FUNCTION) {
+        delete value->data.function_val;
+    }
+
+    // Lists own their elements
+    if (value->type == NAAB_RUST_TYPE_LIST) {
+        for (size_t i = 0; i < value->data.list_val.count; ++i) {
+            naab_rust_value_free(value->data.list_val.items[i]);
+        }
+        delete[] value->data.list_val.items;
+    }
 
     delete value;
 }
 
+NaabRustValue* naab_rust_value_clone(const NaabRustValue* value) {
+    trackAccess("clone", value);
+    if (!value) return nullptr;
+
+    switch (value->type) {
+        case NAAB_RUST_TYPE_INT:
+            return naab_rust_value_create_int(value->data.int_val);
+        case NAAB_RUST_TYPE_DOUBLE:
+            return naab_rust_value_create_double(value->data.double_val);
+        case NAAB_RUST_TYPE_BOOL:
+            return naab_rust_value_create_bool(value->data.bool_val);
+        case NAAB_RUST_TYPE_STRING:
+            return naab_rust_value_create_string(value->data.string_val);
+        case NAAB_RUST_TYPE_INT128:
+            return naab_rust_value_create_int128(value->data.int128_val.high,
+                                                 value->data.int128_val.low);
+        case NAAB_RUST_TYPE_INT64:
+            return naab_rust_value_create_int64(value->data.int64_val);
+        case NAAB_RUST_TYPE_UINT64:
+            return naab_rust_value_create_uint64(value->data.uint64_val);
+        case NAAB_RUST_TYPE_NULL:
+            return naab_rust_value_create_null();
+        case NAAB_RUST_TYPE_HANDLE:
+            return createHandleValue(value->data.handle_val.id,
+                                     value->data.handle_val.type_tag,
+                                     value->data.handle_val.library);
+        case NAAB_RUST_TYPE_FUNCTION:
+            return createFunctionValue(*value->data.function_val);
+        case NAAB_RUST_TYPE_LIST: {
+            size_t count = value->data.list_val.count;
+            std::vector<NaabRustValue*> elements(count, nullptr);
+            for (size_t i = 0; i < count; ++i) {
+                // A NULL copy makes create_list fail and free the others
+                elements[i] = naab_rust_value_clone(value->data.list_val.items[i]);
+            }
+            return naab_rust_value_create_list(elements.data(), count);
+        }
+        default:
+            return naab_rust_value_create_void();
+    }
+}
+
+NaabRustValue* naab_rust_host_getenv(const char* name) {
+    if (!name) return nullptr;
+
+    auto* sandbox = naab::security::ScopedSandbox::getCurrent();
+    if (sandbox && !sandbox->canAccessEnv(name)) {
+        sandbox->logViolation("getenv", name, "SYS_ENV capability required");
+        return nullptr;
+    }
+
+    const char* value = getenv(name);
+    return value ? naab_rust_value_create_string(value) : naab_rust_value_create_void();
+}
+
+// ============================================================================
+// Runtime Version
+// ============================================================================
+
+const char* naab_rust_runtime_version() {
+    return NAAB_VERSION_STRING;
+}
+
+// ============================================================================
+// Host Info
+// ============================================================================
+
+static const char* hostPlatform() {
+#if defined(__ANDROID__)
+    return "android";
+#elif defined(__linux__)
+    return "linux";
+#elif defined(__APPLE__)
+    return "macos";
+#elif defined(_WIN32)
+    return "windows";
+#elif defined(__FreeBSD__)
+    return "freebsd";
+#else
+    return "unknown";
+#endif
+}
+
+static const char* hostArch() {
+#if defined(__x86_64__) || defined(_M_X64)
+    return "x86_64";
+#elif defined(__aarch64__) || defined(_M_ARM64)
+    return "aarch64";
+#elif defined(__arm__) || defined(_M_ARM)
+    return "arm";
+#elif defined(__i386__) || defined(_M_IX86)
+    return "x86";
+#elif defined(__riscv) && __riscv_xlen == 64
+    return "riscv64";
+#else
+    return "unknown";
+#endif
+}
+
+// Points at one of the fixed profile names below, so readers need no lock
+static std::atomic<const char*> sandbox_profile{""};
+
+void naab_rust_host_info(NaabRustHostInfo* info) {
+    if (!info) return;
+
+    // Resolved once; the runtime never changes its temp directory
+    static const std::string temp_dir = naab::paths::temp_dir();
+
+    info->version = NAAB_VERSION_STRING;
+    info->platform = hostPlatform();
+    info->arch = hostArch();
+    info->sandbox_profile = sandbox_profile.load();
+    info->temp_dir = temp_dir.c_str();
+}
+
+// ============================================================================
+// Call-Scoped Context
+// ============================================================================
+
+struct RustCallContext {
+    uint64_t call_id = 0;
+    std::string script_file;
+    uint32_t script_line = 0;
+};
+
+// Set by RustExecutor around each block call on the calling thread
+static thread_local RustCallContext call_context;
+
+uint64_t naab_rust_context_call_id() {
+    return call_context.call_id;
+}
+
+const char* naab_rust_context_script_file() {
+    return call_context.script_file.c_str();
+}
+
+uint32_t naab_rust_context_script_line() {
+    return call_context.script_line;
+}
+
+// ============================================================================
+// Feature Flags
+// ============================================================================
+
+static std::mutex feature_flags_mutex;
+static std::unordered_map<std::string, bool> feature_flags;
+
+bool naab_rust_feature_enabled(const char* name) {
+    if (!name) return false;
+    std::lock_guard<std::mutex> lock(feature_flags_mutex);
+    auto it = feature_flags.find(name);
+    return it != feature_flags.end() && it->second;
+}
+
+// ============================================================================
+// Dispatch Table Support
+// ============================================================================
+
+uint64_t naab_rust_block_name_hash(const char* name) {
+    uint64_t hash = 0xcbf29ce484222325ULL;
+    if (!name) return hash;
+    for (const unsigned char* p = reinterpret_cast<const unsigned char*>(name); *p; ++p) {
+        hash ^= *p;
+        hash *= 0x100000001b3ULL;
+    }
+    return hash;
+}
+
+// ============================================================================
+// Cooperative Interrupt Handling
+// ============================================================================
+
+// Set from the RustExecutor SIGINT handler; lock-free so it is signal-safe
+static std::atomic<bool> rust_interrupted{false};
+
+bool naab_rust_interrupted() {
+    return rust_interrupted.load(std::memory_order_relaxed);
+}
+
+// ============================================================================
+// Sandbox Checks
+// ============================================================================
+
+bool naab_rust_sandbox_can_read(const char* path) {
+    if (!path) return false;
+    auto* sandbox = naab::security::ScopedSandbox::getCurrent();
+    return !sandbox || sandbox->canRead(path);
+}
+
+bool naab_rust_sandbox_can_write(const char* path) {
+    if (!path) return false;
+    auto* sandbox = naab::security::ScopedSandbox::getCurrent();
+    return !sandbox || sandbox->canWrite(path);
+}
+
+// ============================================================================
+// Host CPU Quota
+// ============================================================================
+
+namespace {
+
+// CPUs one cgroup directory's CPU controller allows (v2 cpu.max or v1 CFS
+// files), rounded up; 0 when it sets no quota or can't be read
+unsigned int cgroupDirCpuQuota(const std::string& dir, bool v2) {
+    long long quota = -1;
+    long long period = 0;
+
+    if (v2) {
+        if (FILE* f = std::fopen((dir + "/cpu.max").c_str(), "r")) {
+            char quota_str[32] = {0};
+            if (std::fscanf(f, "%31s %lld", quota_str, &period) == 2 &&
+                std::strcmp(quota_str, "max") != 0) {
+                quota = std::atoll(quota_str);
+            }
+            std::fclose(f);
+        }
+    } else {
+        FILE* q = std::fopen((dir + "/cpu.cfs_quota_us").c_str(), "r");
+        FILE* p = std::fopen((dir + "/cpu.cfs_period_us").c_str(), "r");
+        if (!q || !p || std::fscanf(q, "%lld", &quota) != 1 ||
+            std::fscanf(p, "%lld", &period) != 1) {
+            quota = -1;
+        }
+        if (q) std::fclose(q);
+        if (p) std::fclose(p);
+    }
+
+    if (quota <= 0 || period <= 0) {
+        return 0;
+    }
+    return static_cast<unsigned int>((quota + period - 1) / period);
+}
+
+// CPUs granted to this process by the cgroup CPU controller; 0 if none.
+// Resolves the process's own cgroup from /proc/self/cgroup and takes the
+// tightest quota on the way up to the hierarchy root, since slices and
+// parent cgroups limit their children. Paths missing from the mount (no
+// cgroup namespace) are skipped, leaving the levels that are visible.
+unsigned int cgroupCpuQuota() {
+    std::string mount;
+    std::string path;
+    bool v2 = false;
+
+    if (FILE* f = std::fopen("/proc/self/cgroup", "r")) {
+        char line[4096];
+        while (std::fgets(line, sizeof(line), f)) {
+            // "hierarchy-id:controllers:path"
+            std::string entry(line);
+            if (!entry.empty() && entry.back() == '\n') entry.pop_back();
+            size_t first = entry.find(':');
+            size_t second = first == std::string::npos ? first : entry.find(':', first + 1);
+            if (second == std::string::npos) continue;
+
+            std::string id = entry.substr(0, first);
+            std::string controllers = entry.substr(first + 1, second - first - 1);
+            if (id == "0" && controllers.empty()) {
+                v2 = true;
+                mount = "/sys/fs/cgroup";
+                path = entry.substr(second + 1);
+                continue;  // Hybrid setups may still list a v1 cpu controller, which wins
+            }
+
+            std::string list = "," + controllers + ",";
+            if (list.find(",cpu,") != std::string::npos) {
+                v2 = false;
+                mount = "/sys/fs/cgroup/" + controllers;
+                if (FILE* probe = std::fopen((mount + "/cpu.cfs_period_us").c_str(), "r")) {
+                    std::fclose(probe);
+                } else {
+                    mount = "/sys/fs/cgroup/cpu";
+                }
+                path = entry.substr(second + 1);
+                break;
+            }
+        }
+        std::fclose(f);
+    }
+    if (mount.empty()) {
+        return 0;
+    }
+
+    unsigned int tightest = 0;
+    for (;;) {
+        if (path == "/") path.clear();
+        unsigned int quota = cgroupDirCpuQuota(mount + path, v2);
+        if (quota > 0 && (tightest == 0 || quota < tightest)) {
+            tightest = quota;
+        }
+        if (path.empty()) break;
+        path.erase(path.rfind('/'));
+    }
+    return tightest;
+}
+
+unsigned int computeCpuQuota() {
+    unsigned int cpus = 0;
+#ifdef __linux__
+    cpu_set_t set;
+    if (sched_getaffinity(0, sizeof(set), &set) == 0) {
+        cpus = static_cast<unsigned int>(CPU_COUNT(&set));
+    }
+#endif
+    if (cpus == 0) {
+        cpus = std::thread::hardware_concurrency();
+    }
+
+    unsigned int quota = cgroupCpuQuota();
+    if (quota > 0 && (cpus == 0 || quota < cpus)) {
+        cpus = quota;
+    }
+    return cpus > 0 ? cpus : 1;
+}
+
+} // anonymous namespace
+
+uint32_t naab_rust_host_cpu_quota() {
+    static const unsigned int quota = computeCpuQuota();
+    return quota;
+}
+
 // ============================================================================
 // Conversion Helpers (for RustExecutor)
 // ============================================================================
@@ -119,11 +904,169 @@ void naab_rust_value_free(NaabRustValue* value) {
 namespace naab {
 namespace runtime {
 
+void setRustInterrupted(bool interrupted) {
+    rust_interrupted.store(interrupted, std::memory_order_relaxed);
+}
+
+void setRustCallContext(uint64_t call_id, const std::string& script_file, uint32_t script_line) {
+    call_context.call_id = call_id;
+    call_context.script_file = script_file;
+    call_context.script_line = script_line;
+}
+
+// Record the --sandbox-level profile for naab_rust_host_info; unknown
+// names are reported as ""
+void setRustSandboxProfile(const std::string& profile) {
+    static const char* const profiles[] = {"restricted", "standard", "elevated", "unrestricted"};
+    const char* name = "";
+    for (const char* candidate : profiles) {
+        if (profile == candidate) name = candidate;
+    }
+    sandbox_profile.store(name);
+}
+
+void setRustFeatureFlags(const std::unordered_map<std::string, bool>& flags) {
+    std::lock_guard<std::mutex> lock(feature_flags_mutex);
+    feature_flags = flags;
+}
+
+// Print every value still alive to stderr; returns how many there were.
+// Always 0 unless built with leak checking.
+size_t reportRustValueLeaks() {
+#ifdef NAAB_RUST_LEAK_CHECK
+    std::lock_guard<std::mutex> lock(live_values_mutex);
+    for (const auto& [value, entry] : liveValues()) {
+        fprintf(stderr, "[rust-ffi] leaked value %p from %s, created at:\n",
+                static_cast<const void*>(value), entry.op);
+#ifdef __GLIBC__
+        backtrace_symbols_fd(entry.frames.data(), static_cast<int>(entry.frames.size()), 2);
+#endif
+    }
+    return liveValues().size();
+#else
+    return 0;
+#endif
+}
+
+// Decimal text of a two's complement 128-bit integer. Works on 32-bit
+// limbs so it does not depend on compiler __int128 support.
+static std::string int128ToString(uint64_t high, uint64_t low) {
+    bool negative = (high >> 63) != 0;
+    if (negative) {
+        low = ~low + 1;
+        high = ~high + (low == 0 ? 1 : 0);
+    }
+
+    uint32_t limbs[4] = {
+        static_cast<uint32_t>(high >> 32), static_cast<uint32_t>(high),
+        static_cast<uint32_t>(low >> 32), static_cast<uint32_t>(low)
+    };
+    std::string digits;
+    do {
+        uint64_t remainder = 0;
+        for (auto& limb : limbs) {
+            uint64_t current = (remainder << 32) | limb;
+            limb = static_cast<uint32_t>(current / 10);
+            remainder = current % 10;
+        }
+        digits.push_back(static_cast<char>('0' + remainder));
+    } while (limbs[0] | limbs[1] | limbs[2] | limbs[3]);
+
+    if (negative) digits.push_back('-');
+    return std::string(digits.rbegin(), digits.rend());
+}
+
+// INT128 -> int when it fits (sign-extended from 32 bits), else exact decimal string
+static std::shared_ptr<Value> int128ToValue(uint64_t high, uint64_t low) {
+    bool fits_positive = (high == 0 && low <= static_cast<uint64_t>(INT_MAX));
+    bool fits_negative = (high == ~0ULL && low >= static_cast<uint64_t>(static_cast<int64_t>(INT_MIN)));
+    if (fits_positive || fits_negative) {
+        return std::make_shared<Value>(static_cast<int>(static_cast<int64_t>(low)));
+    }
+    return std::make_shared<Value>(int128ToString(high, low));
+}
+
+// Marks the host dict that stands in for a HANDLE value
+static const char* const kHandleKey = "__rust_handle__";
+
+// String field of a handle dict, or nullptr if missing or not a string
+static const std::string* handleField(const std::unordered_map<std::string, std::shared_ptr<Value>>& dict,
+                                      const char* key) {
+    auto it = dict.find(key);
+    return (it != dict.end() && it->second) ? std::get_if<std::string>(&it->second->data) : nullptr;
+}
+
+// Handles that have reached scripts: id -> (issuing library -> type tag).
+// Script values are editable, so handleToFfi takes library and type only
+// from here; the dict just names the id. The host cannot tell when a
+// library closes a handle, so entries live until the library is shut down
+// or unloaded (forgetRustLibraryHandles).
+static std::mutex issued_handles_mutex;
+static std::unordered_map<uint64_t, std::unordered_map<std::string, std::string>> issued_handles;
+
+static void registerIssuedHandle(uint64_t id, const std::string& library, const char* type_tag) {
+    std::lock_guard<std::mutex> lock(issued_handles_mutex);
+    issued_handles[id][library] = type_tag;
+}
+
+// Host dict -> HANDLE, for dicts produced from a HANDLE by convertFfiValue.
+// Handles only go back to the library that issued them; handles converted
+// outside any block call ("" library) are only accepted outside calls.
+static NaabRustValue* handleToFfi(const std::unordered_map<std::string, std::shared_ptr<Value>>& dict) {
+    const std::string* id_str = handleField(dict, kHandleKey);
+    bool valid = id_str && !id_str->empty() &&
+        std::all_of(id_str->begin(), id_str->end(), [](unsigned char c) { return std::isdigit(c); });
+    errno = 0;
+    unsigned long long id = valid ? std::strtoull(id_str->c_str(), nullptr, 10) : 0;
+    if (!valid || errno == ERANGE) {
+        throw std::runtime_error("Malformed Rust handle: expected a numeric '__rust_handle__' field");
+    }
+
+    std::string library;
+    std::string type_tag;
+    {
+        std::lock_guard<std::mutex> lock(issued_handles_mutex);
+        auto it = issued_handles.find(id);
+        if (it == issued_handles.end()) {
+            throw std::runtime_error("Rust handle " + *id_str + " was not created by any Rust library");
+        }
+        const auto& issuers = it->second;
+
+        auto issuer = issuers.end();
+        if (!current_library.empty()) {
+            issuer = issuers.find(current_library);
+            if (issuer == issuers.end()) {
+                const auto& [other_library, other_type] = *issuers.begin();
+                throw std::runtime_error("Rust handle of type '" + other_type + "' was created by library '" +
+                                         other_library + "' and cannot be passed to library '" +
+                                         current_library + "'");
+            }
+        } else {
+            // No receiving library to match; the dict may pick among real issuers
+            const std::string* named = handleField(dict, "library");
+            issuer = issuers.find(named ? *named : std::string());
+            if (issuer == issuers.end() && issuers.size() == 1) {
+                issuer = issuers.begin();
+            }
+            if (issuer == issuers.end()) {
+                throw std::runtime_error("Rust handle " + *id_str + " was created by more than one library");
+            }
+        }
+        library = issuer->first;
+        type_tag = issuer->second;
+    }
+    return createHandleValue(id, type_tag.c_str(), library.c_str());
+}
+
 // Convert C FFI value to C++ Value
-std::shared_ptr<Value> ffiToValue(NaabRustValue* ffi_val) {
+static std::shared_ptr<Value> convertFfiValue(const NaabRustValue* ffi_val, size_t depth) {
     if (!ffi_val) {
         return std::make_shared<Value>();
     }
+    if (depth > NAAB_RUST_MAX_DEPTH) {
+        throw std::runtime_error("Rust value nested deeper than " +
+                                 std::to_string(NAAB_RUST_MAX_DEPTH) + " levels");
+    }
 
     auto type = naab_rust_value_get_type(ffi_val);
     switch (type) {
@@ -135,17 +1078,64 @@ std::shared_ptr<Value> ffiToValue(NaabRustValue* ffi_val) {
             return std::make_shared<Value>(naab_rust_value_get_bool(ffi_val));
         case NAAB_RUST_TYPE_STRING:
             return std::make_shared<Value>(std::string(naab_rust_value_get_string(ffi_val)));
+        case NAAB_RUST_TYPE_INT128: {
+            uint64_t high = 0, low = 0;
+            naab_rust_value_get_int128(ffi_val, &high, &low);
+            return int128ToValue(high, low);
+        }
+        case NAAB_RUST_TYPE_INT64: {
+            int64_t value = naab_rust_value_get_int64(ffi_val);
+            return int128ToValue(value < 0 ? ~0ULL : 0, static_cast<uint64_t>(value));
+        }
+        case NAAB_RUST_TYPE_UINT64:
+            return int128ToValue(0, naab_rust_value_get_uint64(ffi_val));
+        case NAAB_RUST_TYPE_HANDLE: {
+            uint64_t id = 0;
+            const char* type_tag = "";
+            naab_rust_value_get_handle(ffi_val, &id, &type_tag);
+            // The library whose call handed the value over issued it; the
+            // value's own tag is empty for handles made on a library's
+            // worker threads, so it only counts outside block calls
+            std::string library = current_library.empty()
+                ? std::string(ffi_val->data.handle_val.library) : current_library;
+            registerIssuedHandle(id, library, type_tag);
+            std::unordered_map<std::string, std::shared_ptr<Value>> handle;
+            handle[kHandleKey] = std::make_shared<Value>(std::to_string(id));
+            handle["type"] = std::make_shared<Value>(std::string(type_tag));
+            handle["library"] = std::make_shared<Value>(std::move(library));
+            return std::make_shared<Value>(std::move(handle));
+        }
+        case NAAB_RUST_TYPE_FUNCTION:
+            return *ffi_val->data.function_val;
+        case NAAB_RUST_TYPE_LIST: {
+            size_t length = naab_rust_value_get_length(ffi_val);
+            std::vector<std::shared_ptr<Value>> elements;
+            elements.reserve(length);
+            for (size_t i = 0; i < length; ++i) {
+                elements.push_back(convertFfiValue(naab_rust_value_get_element(ffi_val, i), depth + 1));
+            }
+            return std::make_shared<Value>(std::move(elements));
+        }
         default:
             return std::make_shared<Value>();
     }
 }
 
+std::shared_ptr<Value> ffiToValue(NaabRustValue* ffi_val) {
+    return convertFfiValue(ffi_val, 1);
+}
+
 // Convert C++ Value to C FFI value
-NaabRustValue* valueToFfi(const std::shared_ptr<Value>& val) {
+// `path` holds the lists enclosing val, to detect lists that contain themselves
+static NaabRustValue* convertToFfi(const std::shared_ptr<Value>& val, std::vector<const Value*>& path) {
     if (!val) {
         return naab_rust_value_create_void();
     }
 
+    // A script-level null is passed as an explicit NULL, not as "no value"
+    if (std::holds_alternative<std::monostate>(val->data)) {
+        return naab_rust_value_create_null();
+    }
     if (std::holds_alternative<int>(val->data)) {
         return naab_rust_value_create_int(std::get<int>(val->data));
     }
@@ -158,25 +1148,162 @@ NaabRustValue* valueToFfi(const std::shared_ptr<Value>& val) {
     if (std::holds_alternative<std::string>(val->data)) {
         return naab_rust_value_create_string(std::get<std::string>(val->data).c_str());
     }
+    if (std::holds_alternative<std::shared_ptr<FunctionValue>>(val->data)) {
+        return createFunctionValue(val);
+    }
+    if (const auto* dict = std::get_if<std::unordered_map<std::string, std::shared_ptr<Value>>>(&val->data)) {
+        auto it = dict->find(kHandleKey);
+        if (it != dict->end() && it->second) {
+            return handleToFfi(*dict);
+        }
+    }
+    if (std::holds_alternative<std::vector<std::shared_ptr<Value>>>(val->data)) {
+        if (std::find(path.begin(), path.end(), val.get()) != path.end()) {
+            throw std::runtime_error("Cannot pass a list that contains itself to Rust");
+        }
+        if (path.size() + 1 >= NAAB_RUST_MAX_DEPTH) {  // Same bound create_list enforces
+            throw std::runtime_error("Cannot pass a value nested deeper than " +
+                                     std::to_string(NAAB_RUST_MAX_DEPTH) + " levels to Rust");
+        }
+
+        const auto& list = std::get<std::vector<std::shared_ptr<Value>>>(val->data);
+        std::vector<NaabRustValue*> elements;
+        elements.reserve(list.size());
+        path.push_back(val.get());
+        try {
+            for (const auto& item : list) {
+                // A NULL element makes create_list fail and free the rest
+                elements.push_back(convertToFfi(item, path));
+            }
+        } catch (...) {
+            for (auto* element : elements) {
+                naab_rust_value_free(element);
+            }
+            throw;
+        }
+        path.pop_back();
+        return naab_rust_value_create_list(elements.data(), elements.size());
+    }
     return naab_rust_value_create_void();
 }
 
+// Throws std::runtime_error for self-containing or too deeply nested lists
+NaabRustValue* valueToFfi(const std::shared_ptr<Value>& val) {
+    std::vector<const Value*> path;
+    return convertToFfi(val, path);
+}
+
+// Calls FUNCTION values back into the interpreter running the block call
+using RustFunctionInvoker = std::function<std::shared_ptr<Value>(
+    std::shared_ptr<Value>, const std::vector<std::shared_ptr<Value>>&)>;
+static thread_local RustFunctionInvoker function_invoker;
+
+RustFunctionInvoker setRustFunctionInvoker(RustFunctionInvoker invoker) {
+    std::swap(function_invoker, invoker);
+    return invoker;
+}
+
+// Set the library handles are created by and checked against on this
+// thread; returns the previous one so nested calls can restore it
+std::string setRustCurrentLibrary(std::string library) {
+    std::swap(current_library, library);
+    return library;
+}
+
+// Drop the handles a library issued, once it is shut down or unloaded
+void forgetRustLibraryHandles(const std::string& library) {
+    std::lock_guard<std::mutex> lock(issued_handles_mutex);
+    for (auto it = issued_handles.begin(); it != issued_handles.end();) {
+        it->second.erase(library);
+        it = it->second.empty() ? issued_handles.erase(it) : std::next(it);
+    }
+}
+
 } // namespace runtime
 } // namespace naab
 
 // ============================================================================
-// Error Handling (Phase 4.2.4) - Stub implementations
+// Function Callbacks
 // ============================================================================
-// These will be replaced with actual Rust implementations when integrated
+
+extern "C" NaabRustValue* naab_rust_value_call(const NaabRustValue* function,
+                                               NaabRustValue* const* args,
+                                               size_t arg_count) {
+    trackAccess("call", function);
+    if (!function || function->type != NAAB_RUST_TYPE_FUNCTION || (!args && arg_count > 0)) {
+        naab_rust_report_error(nullptr, "naab_rust_value_call: not a FUNCTION value", nullptr, 0);
+        return nullptr;
+    }
+    if (!naab::runtime::function_invoker) {
+        naab_rust_report_error(nullptr, "naab_rust_value_call: no NAAb interpreter on this thread", nullptr, 0);
+        return nullptr;
+    }
+
+    try {
+        // Arguments are borrowed: converted to host values, never freed here
+        std::vector<std::shared_ptr<Value>> values;
+        values.reserve(arg_count);
+        for (size_t i = 0; i < arg_count; ++i) {
+            values.push_back(naab::runtime::ffiToValue(args[i]));
+        }
+
+        auto result = naab::runtime::function_invoker(*function->data.function_val, values);
+        return naab::runtime::valueToFfi(result);
+    } catch (const std::exception& e) {
+        naab_rust_report_error(nullptr, e.what(), nullptr, 0);
+        return nullptr;
+    }
+}
+
+// ============================================================================
+// Error Handling (Phase 4.2.4)
+// ============================================================================
+// Blocks, or a panic hook installed at library init, report failures with
+// naab_rust_report_error. Each thread keeps its own pending error, so a
+// report only reaches the block call running on the thread that made it;
+// RustExecutor drops stale ones at call start and collects the pending one
+// with naab_rust_get_last_error after a failed call. Reports from threads
+// with no call in progress (e.g. a block's worker thread) are never
+// collected, so they are logged as warnings; the rest at debug level.
+
+struct PendingRustError {
+    NaabRustError* error = nullptr;
+    ~PendingRustError() { naab_rust_error_free(error); }  // Thread exit
+};
+static thread_local PendingRustError pending_error;
 
 extern "C" {
 
-// Stub: Return null error (no error occurred)
+void naab_rust_report_error(const char* block_name, const char* message,
+                            const char* file, uint32_t line) {
+    std::string text = message ? message : "Unknown Rust error";
+    if (block_name && *block_name) {
+        text = std::string("in block '") + block_name + "': " + text;
+    }
+    if (naab_rust_context_call_id() == 0) {
+        LOG_WARN("[rust] {} ({}:{})\n", text, file ? file : "<unknown>", line);
+    } else {
+        LOG_DEBUG("[rust] {} ({}:{})\n", text, file ? file : "<unknown>", line);
+    }
+
+    auto* error = static_cast<NaabRustError*>(malloc(sizeof(NaabRustError)));
+    if (!error) return;
+    error->message = strdup(text.c_str());
+    error->file = file ? strdup(file) : nullptr;
+    error->line = line;
+
+    naab_rust_error_free(pending_error.error);  // Latest report wins
+    pending_error.error = error;
+}
+
+// Take this thread's pending error (caller frees it); null if none was reported
 NaabRustError* naab_rust_get_last_error() {
-    return nullptr;
+    NaabRustError* error = pending_error.error;
+    pending_error.error = nullptr;
+    return error;
 }
 
-// Stub: Free error structure
+// Free error structure
 void naab_rust_error_free(NaabRustError* error) {
     if (error) {
         if (error->message) free(error->message);
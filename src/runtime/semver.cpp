@@ -45,6 +45,25 @@ SemanticVersion SemanticVersion::parse(const std::string& version_str) {
     return SemanticVersion(major, minor, patch, prerelease, build_metadata);
 }
 
+// Parse version in a range requirement, padding partial versions with zeros
+SemanticVersion SemanticVersion::parseRequirement(const std::string& version_str) {
+    std::regex partial_pattern(R"(^(\d+)(?:\.(\d+))?$)");
+
+    std::smatch matches;
+    if (!std::regex_match(version_str, matches, partial_pattern)) {
+        try {
+            return parse(version_str);
+        } catch (const VersionParseException&) {
+            throw VersionParseException(
+                fmt::format("Malformed version requirement: '{}'", version_str));
+        }
+    }
+
+    int major = std::stoi(matches[1].str());
+    int minor = matches[2].matched ? std::stoi(matches[2].str()) : 0;
+    return SemanticVersion(major, minor, 0);
+}
+
 // Convert to string
 std::string SemanticVersion::toString() const {
     std::string result = fmt::format("{}.{}.{}", major, minor, patch);
@@ -179,18 +198,21 @@ bool SemanticVersion::satisfiesRange(const std::string& range) const {
     std::string trimmed_range = range;
     trimmed_range.erase(0, trimmed_range.find_first_not_of(" \t"));
     trimmed_range.erase(trimmed_range.find_last_not_of(" \t") + 1);
+    if (trimmed_range.empty()) {
+        throw VersionParseException("Malformed version requirement: empty range");
+    }
 
     // Handle special range operators
     // ^1.2.3 (caret): >=1.2.3,<2.0.0 (compatible changes)
     if (trimmed_range[0] == '^') {
-        SemanticVersion base = parse(trimmed_range.substr(1));
+        SemanticVersion base = parseRequirement(trimmed_range.substr(1));
         SemanticVersion upper(base.major + 1, 0, 0);
         return *this >= base && *this < upper;
     }
 
     // ~1.2.3 (tilde): >=1.2.3,<1.3.0 (patch-level changes)
     if (trimmed_range[0] == '~') {
-        SemanticVersion base = parse(trimmed_range.substr(1));
+        SemanticVersion base = parseRequirement(trimmed_range.substr(1));
         SemanticVersion upper(base.major, base.minor + 1, 0);
         return *this >= base && *this < upper;
     }
@@ -226,10 +248,16 @@ bool SemanticVersion::satisfiesRange(const std::string& range) const {
         condition.erase(condition.find_last_not_of(" \t") + 1);
         conditions.push_back(condition);
     }
+    if (trimmed_range.back() == ',') {
+        conditions.push_back("");  // getline drops the empty last condition
+    }
 
     // All conditions must be satisfied
     for (const auto& cond : conditions) {
-        if (cond.size() < 2) return false;
+        if (cond.empty()) {
+            throw VersionParseException(
+                fmt::format("Malformed version requirement: empty condition in '{}'", range));
+        }
 
         // Parse operator and version
         std::string op, version_str;
@@ -250,22 +278,19 @@ bool SemanticVersion::satisfiesRange(const std::string& range) const {
         version_str.erase(0, version_str.find_first_not_of(" \t"));
         version_str.erase(version_str.find_last_not_of(" \t") + 1);
 
-        try {
-            SemanticVersion target = parse(version_str);
-
-            // Check condition
-            bool satisfied = false;
-            if (op == ">=") satisfied = (*this >= target);
-            else if (op == ">") satisfied = (*this > target);
-            else if (op == "<=") satisfied = (*this <= target);
-            else if (op == "<") satisfied = (*this < target);
-            else if (op == "=") satisfied = (*this == target);
-            else if (op == "!=") satisfied = (*this != target);
-
-            if (!satisfied) return false;
-        } catch (const VersionParseException&) {
-            return false;  // Invalid version in range
-        }
+        // Malformed versions throw, as for ^ and ~ ranges
+        SemanticVersion target = parseRequirement(version_str);
+
+        // Check condition
+        bool satisfied = false;
+        if (op == ">=") satisfied = (*this >= target);
+        else if (op == ">") satisfied = (*this > target);
+        else if (op == "<=") satisfied = (*this <= target);
+        else if (op == "<") satisfied = (*this < target);
+        else if (op == "=") satisfied = (*this == target);
+        else if (op == "!=") satisfied = (*this != target);
+
+        if (!satisfied) return false;
     }
 
     return true;  // All conditions satisfied
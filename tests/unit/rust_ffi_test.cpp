@@ -3,14 +3,40 @@
 
 #include <gtest/gtest.h>
 #include "naab/rust_ffi.h"
+#include "naab/rust_executor.h"
+#include "naab/config.h"
 #include "naab/interpreter.h"
+#include "naab/manifest.h"
+#include "naab/sandbox.h"
+#include "naab/semver.h"
+#include "naab/stack_tracer.h"
+#include <atomic>
+#include <cfloat>
+#include <climits>
+#include <cmath>
+#include <csignal>
+#include <cstdint>
 #include <cstring>
+#include <filesystem>
+#include <fstream>
+#include <functional>
+#include <limits>
+#include <thread>
 
 // Forward declaration of conversion helpers
 namespace naab {
 namespace runtime {
     std::shared_ptr<interpreter::Value> ffiToValue(NaabRustValue* ffi_val);
     NaabRustValue* valueToFfi(const std::shared_ptr<interpreter::Value>& val);
+    void setRustInterrupted(bool interrupted);
+    void setRustCallContext(uint64_t call_id, const std::string& script_file, uint32_t script_line);
+    void setRustFeatureFlags(const std::unordered_map<std::string, bool>& flags);
+    using RustFunctionInvoker = std::function<std::shared_ptr<interpreter::Value>(
+        std::shared_ptr<interpreter::Value>, const std::vector<std::shared_ptr<interpreter::Value>>&)>;
+    RustFunctionInvoker setRustFunctionInvoker(RustFunctionInvoker invoker);
+    std::string setRustCurrentLibrary(std::string library);
+    void forgetRustLibraryHandles(const std::string& library);
+    void setRustSandboxProfile(const std::string& profile);
 }
 }
 
@@ -64,6 +90,13 @@ TEST(RustFFITest, CreateVoid) {
     naab_rust_value_free(val);
 }
 
+TEST(RustFFITest, CreateNull) {
+    NaabRustValue* val = naab_rust_value_create_null();
+    ASSERT_NE(val, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(val), NAAB_RUST_TYPE_NULL);
+    naab_rust_value_free(val);
+}
+
 // Test type safety - getting wrong type should return default
 TEST(RustFFITest, TypeSafety) {
     NaabRustValue* int_val = naab_rust_value_create_int(42);
@@ -179,3 +212,1024 @@ TEST(RustFFITest, StringMemoryManagement) {
     // If we reach here without crashing, memory management is working
     SUCCEED();
 }
+
+// Test cooperative interrupt flag visible to Rust blocks
+TEST(RustFFITest, InterruptFlag) {
+    EXPECT_FALSE(naab_rust_interrupted());
+
+    naab::runtime::setRustInterrupted(true);
+    EXPECT_TRUE(naab_rust_interrupted());
+
+    naab::runtime::setRustInterrupted(false);
+    EXPECT_FALSE(naab_rust_interrupted());
+}
+
+// Test sandbox path checks exposed to Rust blocks
+TEST(RustFFITest, SandboxPathChecks) {
+    // No active sandbox: everything allowed except null
+    EXPECT_TRUE(naab_rust_sandbox_can_read("/etc/hosts"));
+    EXPECT_TRUE(naab_rust_sandbox_can_write("/tmp/out.txt"));
+    EXPECT_FALSE(naab_rust_sandbox_can_read(nullptr));
+
+    naab::security::SandboxConfig config;
+    config.addCapability(naab::security::Capability::FS_READ);
+    config.allowReadPath("/tmp");
+    naab::security::ScopedSandbox scoped(config);
+
+    EXPECT_TRUE(naab_rust_sandbox_can_read("/tmp/data.txt"));
+    EXPECT_FALSE(naab_rust_sandbox_can_read("/etc/hosts"));
+    EXPECT_FALSE(naab_rust_sandbox_can_write("/tmp/out.txt"));
+}
+
+// Test block name hash used by naab_rust_dispatch (64-bit FNV-1a)
+TEST(RustFFITest, BlockNameHash) {
+    EXPECT_EQ(naab_rust_block_name_hash(""), 0xcbf29ce484222325ULL);
+    EXPECT_EQ(naab_rust_block_name_hash("a"), 0xaf63dc4c8601ec8cULL);
+    EXPECT_EQ(naab_rust_block_name_hash("foobar"), 0x85944171f73967e8ULL);
+    EXPECT_NE(naab_rust_block_name_hash("add"), naab_rust_block_name_hash("sub"));
+}
+
+// Test chunked string building
+TEST(RustFFITest, ChunkedStringBuilder) {
+    NaabRustStringBuilder* builder = naab_rust_string_begin(4);
+    ASSERT_NE(builder, nullptr);
+
+    std::string expected;
+    for (int i = 0; i < 1000; ++i) {
+        std::string chunk = "chunk" + std::to_string(i) + ";";
+        ASSERT_TRUE(naab_rust_string_append_chunk(builder, chunk.data(), chunk.size()));
+        expected += chunk;
+    }
+    EXPECT_TRUE(naab_rust_string_append_chunk(builder, nullptr, 0));
+    EXPECT_FALSE(naab_rust_string_append_chunk(builder, nullptr, 3));
+
+    NaabRustValue* val = naab_rust_string_finish(builder);
+    ASSERT_NE(val, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(val), NAAB_RUST_TYPE_STRING);
+    EXPECT_EQ(std::string(naab_rust_value_get_string(val)), expected);
+    naab_rust_value_free(val);
+
+    // Abandoned builders release their buffer
+    builder = naab_rust_string_begin(0);
+    ASSERT_TRUE(naab_rust_string_append_chunk(builder, "partial", 7));
+    naab_rust_string_abort(builder);
+}
+
+// Test runtime version reported to Rust blocks
+TEST(RustFFITest, RuntimeVersion) {
+    ASSERT_NE(naab_rust_runtime_version(), nullptr);
+    EXPECT_STREQ(naab_rust_runtime_version(), NAAB_VERSION_STRING);
+}
+
+// Test error reporting from Rust blocks (e.g. a panic hook)
+TEST(RustFFITest, ReportedErrorIsCollectedOnce) {
+    EXPECT_EQ(naab_rust_get_last_error(), nullptr);
+
+    naab_rust_report_error("resize", "index out of bounds", "src/lib.rs", 42);
+
+    NaabRustError* error = naab_rust_get_last_error();
+    ASSERT_NE(error, nullptr);
+    EXPECT_STREQ(error->message, "in block 'resize': index out of bounds");
+    EXPECT_STREQ(error->file, "src/lib.rs");
+    EXPECT_EQ(error->line, 42u);
+    naab_rust_error_free(error);
+
+    EXPECT_EQ(naab_rust_get_last_error(), nullptr);
+
+    // A report made on another thread never reaches this thread's calls
+    std::thread worker([] {
+        naab_rust_report_error("worker", "panicked", nullptr, 0);
+    });
+    worker.join();
+    EXPECT_EQ(naab_rust_get_last_error(), nullptr);
+}
+
+// Test environment reads routed through the sandbox
+TEST(RustFFITest, HostGetenv) {
+    setenv("NAAB_RUST_FFI_TEST_VAR", "present", 1);
+    unsetenv("NAAB_RUST_FFI_TEST_UNSET");
+
+    NaabRustValue* val = naab_rust_host_getenv("NAAB_RUST_FFI_TEST_VAR");
+    ASSERT_NE(val, nullptr);
+    EXPECT_STREQ(naab_rust_value_get_string(val), "present");
+    naab_rust_value_free(val);
+
+    val = naab_rust_host_getenv("NAAB_RUST_FFI_TEST_UNSET");
+    ASSERT_NE(val, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(val), NAAB_RUST_TYPE_VOID);
+    naab_rust_value_free(val);
+
+    // Sandbox without SYS_ENV denies access
+    naab::security::SandboxConfig config;
+    naab::security::ScopedSandbox scoped(config);
+    EXPECT_EQ(naab_rust_host_getenv("NAAB_RUST_FFI_TEST_VAR"), nullptr);
+}
+
+// Test 128-bit integers: halves round-trip, int when it fits, exact string otherwise
+TEST(RustFFITest, Int128Conversion) {
+    using namespace naab;
+
+    NaabRustValue* val = naab_rust_value_create_int128(0x0123456789abcdefULL, 0xfedcba9876543210ULL);
+    ASSERT_NE(val, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(val), NAAB_RUST_TYPE_INT128);
+    uint64_t high = 0, low = 0;
+    ASSERT_TRUE(naab_rust_value_get_int128(val, &high, &low));
+    EXPECT_EQ(high, 0x0123456789abcdefULL);
+    EXPECT_EQ(low, 0xfedcba9876543210ULL);
+    naab_rust_value_free(val);
+
+    auto convert = [](uint64_t h, uint64_t l) {
+        NaabRustValue* v = naab_rust_value_create_int128(h, l);
+        auto result = runtime::ffiToValue(v);
+        naab_rust_value_free(v);
+        return result;
+    };
+
+    auto small = convert(~0ULL, static_cast<uint64_t>(-5));
+    ASSERT_TRUE(std::holds_alternative<int>(small->data));
+    EXPECT_EQ(std::get<int>(small->data), -5);
+
+    auto max = convert(0x7fffffffffffffffULL, ~0ULL);
+    ASSERT_TRUE(std::holds_alternative<std::string>(max->data));
+    EXPECT_EQ(std::get<std::string>(max->data), "170141183460469231731687303715884105727");
+
+    auto min = convert(0x8000000000000000ULL, 0);
+    ASSERT_TRUE(std::holds_alternative<std::string>(min->data));
+    EXPECT_EQ(std::get<std::string>(min->data), "-170141183460469231731687303715884105728");
+
+    // Just past the int range stays exact
+    auto big = convert(0, 0x80000000ULL);
+    ASSERT_TRUE(std::holds_alternative<std::string>(big->data));
+    EXPECT_EQ(std::get<std::string>(big->data), "2147483648");
+
+    // Wrong type leaves outputs untouched
+    NaabRustValue* int_val = naab_rust_value_create_int(1);
+    EXPECT_FALSE(naab_rust_value_get_int128(int_val, &high, &low));
+    naab_rust_value_free(int_val);
+}
+
+// Test call-scoped context visible to Rust blocks
+TEST(RustFFITest, CallContext) {
+    EXPECT_EQ(naab_rust_context_call_id(), 0u);
+    EXPECT_STREQ(naab_rust_context_script_file(), "");
+    EXPECT_EQ(naab_rust_context_script_line(), 0u);
+
+    naab::runtime::setRustCallContext(17, "scripts/resize.naab", 42);
+    EXPECT_EQ(naab_rust_context_call_id(), 17u);
+    EXPECT_STREQ(naab_rust_context_script_file(), "scripts/resize.naab");
+    EXPECT_EQ(naab_rust_context_script_line(), 42u);
+
+    naab::runtime::setRustCallContext(0, "", 0);
+    EXPECT_EQ(naab_rust_context_call_id(), 0u);
+}
+
+// Test the CPU count reported for block-side thread pools
+TEST(RustFFITest, HostCpuQuota) {
+    uint32_t cpus = naab_rust_host_cpu_quota();
+    EXPECT_GE(cpus, 1u);
+    EXPECT_EQ(naab_rust_host_cpu_quota(), cpus);
+}
+
+// Test feature flags set from the manifest
+TEST(RustFFITest, FeatureFlags) {
+    naab::runtime::setRustFeatureFlags({{"new_parser", true}, {"legacy_io", false}});
+
+    EXPECT_TRUE(naab_rust_feature_enabled("new_parser"));
+    EXPECT_FALSE(naab_rust_feature_enabled("legacy_io"));
+    EXPECT_FALSE(naab_rust_feature_enabled("unknown"));
+    EXPECT_FALSE(naab_rust_feature_enabled(nullptr));
+
+    naab::runtime::setRustFeatureFlags({});
+    EXPECT_FALSE(naab_rust_feature_enabled("new_parser"));
+}
+
+// Test list values and their conversion in both directions
+TEST(RustFFITest, ListValues) {
+    using namespace naab;
+
+    NaabRustValue* elements[] = {
+        naab_rust_value_create_int(1),
+        naab_rust_value_create_string("two"),
+    };
+    NaabRustValue* list = naab_rust_value_create_list(elements, 2);
+    ASSERT_NE(list, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(list), NAAB_RUST_TYPE_LIST);
+    ASSERT_EQ(naab_rust_value_get_length(list), 2u);
+    EXPECT_EQ(naab_rust_value_get_int(naab_rust_value_get_element(list, 0)), 1);
+    EXPECT_STREQ(naab_rust_value_get_string(naab_rust_value_get_element(list, 1)), "two");
+    EXPECT_EQ(naab_rust_value_get_element(list, 2), nullptr);
+
+    auto converted = runtime::ffiToValue(list);
+    naab_rust_value_free(list);  // Frees the elements too
+    ASSERT_TRUE(std::holds_alternative<std::vector<std::shared_ptr<interpreter::Value>>>(converted->data));
+    const auto& items = std::get<std::vector<std::shared_ptr<interpreter::Value>>>(converted->data);
+    ASSERT_EQ(items.size(), 2u);
+    EXPECT_EQ(std::get<int>(items[0]->data), 1);
+    EXPECT_EQ(std::get<std::string>(items[1]->data), "two");
+
+    // Nested host list round-trips through the FFI
+    std::vector<std::shared_ptr<interpreter::Value>> inner = {std::make_shared<interpreter::Value>(true)};
+    std::vector<std::shared_ptr<interpreter::Value>> outer = {
+        std::make_shared<interpreter::Value>(std::move(inner)),
+        std::make_shared<interpreter::Value>(2.5),
+    };
+    NaabRustValue* nested = runtime::valueToFfi(std::make_shared<interpreter::Value>(std::move(outer)));
+    ASSERT_NE(nested, nullptr);
+    ASSERT_EQ(naab_rust_value_get_length(nested), 2u);
+    const NaabRustValue* first = naab_rust_value_get_element(nested, 0);
+    EXPECT_EQ(naab_rust_value_get_type(first), NAAB_RUST_TYPE_LIST);
+    EXPECT_TRUE(naab_rust_value_get_bool(naab_rust_value_get_element(first, 0)));
+    EXPECT_DOUBLE_EQ(naab_rust_value_get_double(naab_rust_value_get_element(nested, 1)), 2.5);
+    naab_rust_value_free(nested);
+
+    // Empty list, and a NULL element rejects the whole list
+    NaabRustValue* empty = naab_rust_value_create_list(nullptr, 0);
+    ASSERT_NE(empty, nullptr);
+    EXPECT_EQ(naab_rust_value_get_length(empty), 0u);
+    naab_rust_value_free(empty);
+
+    NaabRustValue* partial[] = {naab_rust_value_create_int(1), nullptr};
+    EXPECT_EQ(naab_rust_value_create_list(partial, 2), nullptr);
+}
+
+// Test 64-bit integers and their overflow behavior on conversion
+TEST(RustFFITest, Int64Conversion) {
+    using namespace naab;
+
+    NaabRustValue* val = naab_rust_value_create_int64(INT64_MIN);
+    ASSERT_NE(val, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(val), NAAB_RUST_TYPE_INT64);
+    EXPECT_EQ(naab_rust_value_get_int64(val), INT64_MIN);
+    EXPECT_EQ(naab_rust_value_get_int(val), 0);  // Not an INT
+    naab_rust_value_free(val);
+
+    NaabRustValue* narrow = naab_rust_value_create_int(-7);
+    EXPECT_EQ(naab_rust_value_get_int64(narrow), -7);
+    naab_rust_value_free(narrow);
+
+    auto convert = [](int64_t value) {
+        NaabRustValue* v = naab_rust_value_create_int64(value);
+        auto result = runtime::ffiToValue(v);
+        naab_rust_value_free(v);
+        return result;
+    };
+
+    auto fits = convert(INT_MIN);
+    ASSERT_TRUE(std::holds_alternative<int>(fits->data));
+    EXPECT_EQ(std::get<int>(fits->data), INT_MIN);
+
+    auto large = convert(static_cast<int64_t>(INT_MAX) + 1);
+    ASSERT_TRUE(std::holds_alternative<std::string>(large->data));
+    EXPECT_EQ(std::get<std::string>(large->data), "2147483648");
+
+    auto min = convert(INT64_MIN);
+    ASSERT_TRUE(std::holds_alternative<std::string>(min->data));
+    EXPECT_EQ(std::get<std::string>(min->data), "-9223372036854775808");
+}
+
+// Test that script nulls stay distinct from a missing value
+TEST(RustFFITest, ConversionExplicitNull) {
+    using namespace naab;
+
+    NaabRustValue* ffi_val = runtime::valueToFfi(std::make_shared<interpreter::Value>());
+    ASSERT_NE(ffi_val, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(ffi_val), NAAB_RUST_TYPE_NULL);
+
+    auto cpp_val = runtime::ffiToValue(ffi_val);
+    EXPECT_TRUE(std::holds_alternative<std::monostate>(cpp_val->data));
+    naab_rust_value_free(ffi_val);
+}
+
+// Test deep clone and the nesting limits of conversion
+TEST(RustFFITest, NestedValues) {
+    using namespace naab;
+    using List = std::vector<std::shared_ptr<interpreter::Value>>;
+
+    NaabRustValue* inner[] = {naab_rust_value_create_string("leaf")};
+    NaabRustValue* outer[] = {naab_rust_value_create_list(inner, 1), naab_rust_value_create_int64(9)};
+    NaabRustValue* original = naab_rust_value_create_list(outer, 2);
+    ASSERT_NE(original, nullptr);
+
+    NaabRustValue* copy = naab_rust_value_clone(original);
+    naab_rust_value_free(original);  // The copy shares nothing with it
+    ASSERT_NE(copy, nullptr);
+    const NaabRustValue* copied_inner = naab_rust_value_get_element(copy, 0);
+    EXPECT_STREQ(naab_rust_value_get_string(naab_rust_value_get_element(copied_inner, 0)), "leaf");
+    EXPECT_EQ(naab_rust_value_get_int64(naab_rust_value_get_element(copy, 1)), 9);
+    naab_rust_value_free(copy);
+
+    // A host list that contains itself is rejected, not followed forever
+    auto cyclic = std::make_shared<interpreter::Value>(List{});
+    std::get<List>(cyclic->data).push_back(cyclic);
+    EXPECT_THROW(runtime::valueToFfi(cyclic), std::runtime_error);
+    std::get<List>(cyclic->data).clear();  // Break the cycle so it is released
+
+    // The same list twice is not a cycle
+    auto shared = std::make_shared<interpreter::Value>(List{std::make_shared<interpreter::Value>(1)});
+    NaabRustValue* twice = runtime::valueToFfi(std::make_shared<interpreter::Value>(List{shared, shared}));
+    ASSERT_NE(twice, nullptr);
+    EXPECT_EQ(naab_rust_value_get_length(twice), 2u);
+    naab_rust_value_free(twice);
+
+    // Nesting past NAAB_RUST_MAX_DEPTH fails in both directions
+    auto deep = std::make_shared<interpreter::Value>(1);
+    for (int i = 0; i <= NAAB_RUST_MAX_DEPTH; ++i) {
+        deep = std::make_shared<interpreter::Value>(List{deep});
+    }
+    EXPECT_THROW(runtime::valueToFfi(deep), std::runtime_error);
+
+    // create_list refuses to build past the limit, so free/clone stay bounded
+    NaabRustValue* deep_ffi = naab_rust_value_create_int(1);
+    for (int i = 1; i < NAAB_RUST_MAX_DEPTH; ++i) {
+        deep_ffi = naab_rust_value_create_list(&deep_ffi, 1);
+        ASSERT_NE(deep_ffi, nullptr);
+    }
+    NaabRustValue* deepest = naab_rust_value_clone(deep_ffi);
+    ASSERT_NE(deepest, nullptr);
+    EXPECT_NO_THROW(runtime::ffiToValue(deepest));
+    EXPECT_EQ(naab_rust_value_create_list(&deepest, 1), nullptr);  // Frees deepest
+    naab_rust_value_free(deep_ffi);
+}
+
+// Test opaque handles and their round trip through a script value
+TEST(RustFFITest, HandleValues) {
+    using namespace naab;
+    using Dict = std::unordered_map<std::string, std::shared_ptr<interpreter::Value>>;
+
+    NaabRustValue* handle = naab_rust_value_create_handle(0xfedcba9876543210ULL, "db::Connection");
+    ASSERT_NE(handle, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(handle), NAAB_RUST_TYPE_HANDLE);
+
+    auto script_value = runtime::ffiToValue(handle);
+    naab_rust_value_free(handle);
+    ASSERT_TRUE(std::holds_alternative<Dict>(script_value->data));
+    const auto& dict = std::get<Dict>(script_value->data);
+    EXPECT_EQ(std::get<std::string>(dict.at("__rust_handle__")->data), "18364758544493064720");
+    EXPECT_EQ(std::get<std::string>(dict.at("type")->data), "db::Connection");
+
+    NaabRustValue* back = runtime::valueToFfi(script_value);
+    ASSERT_NE(back, nullptr);
+    uint64_t id = 0;
+    const char* type_tag = nullptr;
+    ASSERT_TRUE(naab_rust_value_get_handle(back, &id, &type_tag));
+    EXPECT_EQ(id, 0xfedcba9876543210ULL);
+    EXPECT_STREQ(type_tag, "db::Connection");
+    naab_rust_value_free(back);
+
+    // A tampered handle is rejected instead of reaching the library
+    Dict forged = {
+        {"__rust_handle__", std::make_shared<interpreter::Value>(std::string("12abc"))},
+        {"type", std::make_shared<interpreter::Value>(std::string("db::Connection"))},
+    };
+    EXPECT_THROW(runtime::valueToFfi(std::make_shared<interpreter::Value>(forged)), std::runtime_error);
+
+    NaabRustValue* number = naab_rust_value_create_int(1);
+    EXPECT_FALSE(naab_rust_value_get_handle(number, &id, &type_tag));
+    naab_rust_value_free(number);
+}
+
+// Test calling a NAAb function passed to a block
+TEST(RustFFITest, FunctionCallback) {
+    using namespace naab;
+    using interpreter::Value;
+
+    auto fn = std::make_shared<Value>(std::make_shared<interpreter::FunctionValue>(
+        "double_it", std::vector<std::string>{"x"}, std::vector<ast::Type>{},
+        std::vector<ast::Expr*>{}, nullptr));
+
+    NaabRustValue* ffi_fn = runtime::valueToFfi(fn);
+    ASSERT_NE(ffi_fn, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(ffi_fn), NAAB_RUST_TYPE_FUNCTION);
+    EXPECT_EQ(runtime::ffiToValue(ffi_fn), fn);  // Same function comes back
+
+    NaabRustValue* arg = naab_rust_value_create_int(21);
+
+    // Without an interpreter on this thread the call fails cleanly
+    EXPECT_EQ(naab_rust_value_call(ffi_fn, &arg, 1), nullptr);
+    naab_rust_error_free(naab_rust_get_last_error());
+
+    std::shared_ptr<Value> called;
+    auto previous = runtime::setRustFunctionInvoker(
+        [&](std::shared_ptr<Value> f, const std::vector<std::shared_ptr<Value>>& args) {
+            called = f;
+            return std::make_shared<Value>(std::get<int>(args.at(0)->data) * 2);
+        });
+
+    NaabRustValue* copy = naab_rust_value_clone(ffi_fn);
+    naab_rust_value_free(ffi_fn);
+    NaabRustValue* result = naab_rust_value_call(copy, &arg, 1);
+    ASSERT_NE(result, nullptr);
+    EXPECT_EQ(naab_rust_value_get_int(result), 42);
+    EXPECT_EQ(called, fn);
+    EXPECT_EQ(naab_rust_get_last_error(), nullptr);  // Nothing reported
+    naab_rust_value_free(result);
+
+    EXPECT_EQ(naab_rust_value_call(arg, nullptr, 0), nullptr);  // Not a function
+    naab_rust_error_free(naab_rust_get_last_error());
+
+    runtime::setRustFunctionInvoker(previous);
+    naab_rust_value_free(arg);
+    naab_rust_value_free(copy);
+}
+
+// Test the runtime requirement ranges Rust libraries declare
+TEST(RustFFITest, RuntimeRequirementRanges) {
+    using naab::versioning::SemanticVersion;
+    using naab::versioning::VersionParseException;
+
+    SemanticVersion runtime(1, 5, 2);
+    EXPECT_TRUE(runtime.satisfiesRange(">=1.4, <2"));
+    EXPECT_FALSE(runtime.satisfiesRange(">=1.6, <2"));
+    EXPECT_TRUE(runtime.satisfiesRange("^1.4"));
+    EXPECT_TRUE(runtime.satisfiesRange("~1.5"));
+    EXPECT_FALSE(runtime.satisfiesRange("<1"));
+
+    // Malformed requirements throw whatever the operator
+    EXPECT_THROW(runtime.satisfiesRange(">=1.4.x.2"), VersionParseException);
+    EXPECT_THROW(runtime.satisfiesRange("^one"), VersionParseException);
+    EXPECT_THROW(runtime.satisfiesRange(">=1.4,"), VersionParseException);
+}
+
+// Test that handles only go back to the library that created them
+TEST(RustFFITest, HandleLibraryTag) {
+    using namespace naab;
+    using Dict = std::unordered_map<std::string, std::shared_ptr<interpreter::Value>>;
+
+    runtime::setRustCurrentLibrary("./libs/db.so");
+    NaabRustValue* handle = naab_rust_value_create_handle(7, "db::Connection");
+    auto script_value = runtime::ffiToValue(handle);
+    naab_rust_value_free(handle);
+    EXPECT_EQ(std::get<std::string>(std::get<Dict>(script_value->data).at("library")->data), "./libs/db.so");
+
+    // Same library: accepted
+    NaabRustValue* back = runtime::valueToFfi(script_value);
+    ASSERT_NE(back, nullptr);
+    naab_rust_value_free(back);
+
+    // Another library: rejected before the block sees it
+    runtime::setRustCurrentLibrary("./libs/other.so");
+    EXPECT_THROW(runtime::valueToFfi(script_value), std::runtime_error);
+
+    // Editing the dict does not help: library and type come from the host's records
+    auto& fields = std::get<Dict>(script_value->data);
+    fields.erase("library");
+    EXPECT_THROW(runtime::valueToFfi(script_value), std::runtime_error);
+    fields["library"] = std::make_shared<interpreter::Value>(std::string("./libs/other.so"));
+    EXPECT_THROW(runtime::valueToFfi(script_value), std::runtime_error);
+
+    runtime::setRustCurrentLibrary("./libs/db.so");
+    fields["type"] = std::make_shared<interpreter::Value>(std::string("fs::File"));
+    back = runtime::valueToFfi(script_value);
+    ASSERT_NE(back, nullptr);
+    uint64_t id = 0;
+    const char* type_tag = nullptr;
+    ASSERT_TRUE(naab_rust_value_get_handle(back, &id, &type_tag));
+    EXPECT_STREQ(type_tag, "db::Connection");
+    naab_rust_value_free(back);
+
+    // Ids the library never handed out are refused
+    fields[std::string("__rust_handle__")] = std::make_shared<interpreter::Value>(std::string("8"));
+    EXPECT_THROW(runtime::valueToFfi(script_value), std::runtime_error);
+
+    // A handle made on a library worker thread belongs to the library that returned it
+    NaabRustValue* worker_handle = nullptr;
+    std::thread([&] { worker_handle = naab_rust_value_create_handle(9, "db::Cursor"); }).join();
+    ASSERT_NE(worker_handle, nullptr);
+    auto worker_value = runtime::ffiToValue(worker_handle);
+    naab_rust_value_free(worker_handle);
+    EXPECT_EQ(std::get<std::string>(std::get<Dict>(worker_value->data).at("library")->data), "./libs/db.so");
+    runtime::setRustCurrentLibrary("./libs/other.so");
+    EXPECT_THROW(runtime::valueToFfi(worker_value), std::runtime_error);
+
+    // Unloading the library forgets its handles
+    runtime::setRustCurrentLibrary("./libs/db.so");
+    back = runtime::valueToFfi(worker_value);
+    ASSERT_NE(back, nullptr);
+    naab_rust_value_free(back);
+    runtime::forgetRustLibraryHandles("./libs/db.so");
+    EXPECT_THROW(runtime::valueToFfi(worker_value), std::runtime_error);
+
+    runtime::setRustCurrentLibrary("");
+}
+
+// Test that chunked strings stop growing on interrupt or at the sandbox limit
+TEST(RustFFITest, ChunkedStringBuilderLimits) {
+    NaabRustStringBuilder* builder = naab_rust_string_begin(0);
+    ASSERT_TRUE(naab_rust_string_append_chunk(builder, "a", 1));
+
+    naab::runtime::setRustInterrupted(true);
+    EXPECT_FALSE(naab_rust_string_append_chunk(builder, "b", 1));
+    naab::runtime::setRustInterrupted(false);
+    EXPECT_TRUE(naab_rust_string_append_chunk(builder, "b", 1));
+
+    {
+        naab::security::SandboxConfig config;
+        config.max_memory_mb = 1;
+        naab::security::ScopedSandbox scoped(config);
+
+        std::string chunk(512 * 1024, 'x');
+        EXPECT_TRUE(naab_rust_string_append_chunk(builder, chunk.data(), chunk.size()));
+        EXPECT_FALSE(naab_rust_string_append_chunk(builder, chunk.data(), chunk.size()));
+
+        // The up-front hint counts against the limit too
+        EXPECT_EQ(naab_rust_string_begin(2 * 1024 * 1024), nullptr);
+    }
+
+    EXPECT_EQ(naab_rust_string_begin(SIZE_MAX), nullptr);  // hint + 1 overflows
+
+    NaabRustValue* val = naab_rust_string_finish(builder);
+    ASSERT_NE(val, nullptr);
+    EXPECT_EQ(std::strlen(naab_rust_value_get_string(val)), 2u + 512 * 1024);
+    naab_rust_value_free(val);
+}
+
+// Test unsigned 64-bit integers and their conversion rules
+TEST(RustFFITest, UInt64Conversion) {
+    using namespace naab;
+
+    NaabRustValue* val = naab_rust_value_create_uint64(UINT64_MAX);
+    ASSERT_NE(val, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(val), NAAB_RUST_TYPE_UINT64);
+    EXPECT_EQ(naab_rust_value_get_uint64(val), UINT64_MAX);
+    EXPECT_EQ(naab_rust_value_get_int64(val), 0);  // Not an INT64
+
+    auto large = runtime::ffiToValue(val);
+    ASSERT_TRUE(std::holds_alternative<std::string>(large->data));
+    EXPECT_EQ(std::get<std::string>(large->data), "18446744073709551615");
+    naab_rust_value_free(val);
+
+    val = naab_rust_value_create_uint64(42);
+    auto small = runtime::ffiToValue(val);
+    ASSERT_TRUE(std::holds_alternative<int>(small->data));
+    EXPECT_EQ(std::get<int>(small->data), 42);
+    naab_rust_value_free(val);
+
+    // Signed values widen only when non-negative
+    NaabRustValue* positive = naab_rust_value_create_int(7);
+    NaabRustValue* negative = naab_rust_value_create_int64(-7);
+    EXPECT_EQ(naab_rust_value_get_uint64(positive), 7u);
+    EXPECT_EQ(naab_rust_value_get_uint64(negative), 0u);
+    naab_rust_value_free(positive);
+    naab_rust_value_free(negative);
+}
+
+// Test f32 values and their documented rounding
+TEST(RustFFITest, FloatPrecision) {
+    NaabRustValue* val = naab_rust_value_create_float(0.1f);
+    ASSERT_NE(val, nullptr);
+    EXPECT_EQ(naab_rust_value_get_type(val), NAAB_RUST_TYPE_DOUBLE);
+    EXPECT_EQ(naab_rust_value_get_double(val), static_cast<double>(0.1f));  // Exact widening
+    EXPECT_EQ(naab_rust_value_get_float(val), 0.1f);                         // Round trip
+    naab_rust_value_free(val);
+
+    val = naab_rust_value_create_double(0.1);
+    EXPECT_EQ(naab_rust_value_get_float(val), 0.1f);  // Nearest f32
+    naab_rust_value_free(val);
+
+    val = naab_rust_value_create_double(1e300);
+    EXPECT_EQ(naab_rust_value_get_float(val), std::numeric_limits<float>::infinity());
+    naab_rust_value_free(val);
+
+    val = naab_rust_value_create_double(-1e300);
+    EXPECT_EQ(naab_rust_value_get_float(val), -std::numeric_limits<float>::infinity());
+    naab_rust_value_free(val);
+
+    // Just past FLT_MAX rounds back down; the halfway point ties to infinity
+    const double half_ulp = std::ldexp(1.0, 103);
+    val = naab_rust_value_create_double(static_cast<double>(FLT_MAX) + half_ulp / 2);
+    EXPECT_EQ(naab_rust_value_get_float(val), FLT_MAX);
+    naab_rust_value_free(val);
+
+    val = naab_rust_value_create_double(-(static_cast<double>(FLT_MAX) + half_ulp / 2));
+    EXPECT_EQ(naab_rust_value_get_float(val), -FLT_MAX);
+    naab_rust_value_free(val);
+
+    val = naab_rust_value_create_double(static_cast<double>(FLT_MAX) + half_ulp);
+    EXPECT_EQ(naab_rust_value_get_float(val), std::numeric_limits<float>::infinity());
+    naab_rust_value_free(val);
+
+    val = naab_rust_value_create_double(std::numeric_limits<double>::quiet_NaN());
+    EXPECT_TRUE(std::isnan(naab_rust_value_get_float(val)));
+    naab_rust_value_free(val);
+
+    val = naab_rust_value_create_int(3);
+    EXPECT_EQ(naab_rust_value_get_float(val), 0.0f);
+    naab_rust_value_free(val);
+}
+
+// Test host environment info reported to Rust blocks
+TEST(RustFFITest, HostInfo) {
+    NaabRustHostInfo info;
+    naab_rust_host_info(&info);
+    EXPECT_STREQ(info.version, NAAB_VERSION_STRING);
+    ASSERT_NE(info.platform, nullptr);
+    ASSERT_NE(info.arch, nullptr);
+    EXPECT_STRNE(info.platform, "");
+    EXPECT_STRNE(info.arch, "");
+    ASSERT_NE(info.temp_dir, nullptr);
+    EXPECT_STRNE(info.temp_dir, "");
+    EXPECT_STREQ(info.sandbox_profile, "");
+
+    naab::runtime::setRustSandboxProfile("restricted");
+    naab_rust_host_info(&info);
+    EXPECT_STREQ(info.sandbox_profile, "restricted");
+
+    naab::runtime::setRustSandboxProfile("bogus");
+    naab_rust_host_info(&info);
+    EXPECT_STREQ(info.sandbox_profile, "");
+
+    naab_rust_host_info(nullptr);  // Ignored
+}
+
+// Blocks for the executor tests, registered as statically linked blocks
+static NaabRustValue* staticDoubleIt(NaabRustValue** args, size_t arg_count) {
+    if (arg_count != 1) return nullptr;
+    return naab_rust_value_create_int(naab_rust_value_get_int(args[0]) * 2);
+}
+
+static NaabRustValue* staticTripleIt(NaabRustValue** args, size_t arg_count) {
+    if (arg_count != 1) return nullptr;
+    return naab_rust_value_create_int(naab_rust_value_get_int(args[0]) * 3);
+}
+
+// Forwards its arguments to the block named by the first one
+static NaabRustValue* staticForward(NaabRustValue** args, size_t arg_count) {
+    if (arg_count < 1) return nullptr;
+    NaabRustValue* result = naab_rust_host_call(naab_rust_value_get_string(args[0]),
+                                                args + 1, arg_count - 1);
+    if (!result) return nullptr;
+
+    // Still ours after the call: borrowed, not consumed
+    int sum = naab_rust_value_get_int(result) + naab_rust_value_get_int(args[1]);
+    naab_rust_value_free(result);
+    return naab_rust_value_create_int(sum);
+}
+
+// Test statically registered blocks called through the executor
+TEST(RustFFITest, StaticBlockThroughExecutor) {
+    using namespace naab;
+    using interpreter::Value;
+
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "double_it", staticDoubleIt));
+    EXPECT_FALSE(naab_rust_register_static_block("static:test", "double_it", staticDoubleIt));
+    EXPECT_FALSE(naab_rust_register_static_block("static_test", nullptr, staticDoubleIt));
+
+    runtime::RustExecutor executor;
+    auto result = executor.executeBlock("rust://static_test::double_it", {std::make_shared<Value>(21)});
+    ASSERT_TRUE(std::holds_alternative<int>(result->data));
+    EXPECT_EQ(std::get<int>(result->data), 42);
+
+    // Unregistered names in a library that does not exist still fail to load
+    EXPECT_THROW(executor.executeBlock("rust://static_test::missing", {}), std::runtime_error);
+
+    // Re-registering replaces the block, even for an executor that already called it
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "double_it", staticTripleIt));
+    result = executor.executeBlock("rust://static_test::double_it", {std::make_shared<Value>(21)});
+    EXPECT_EQ(std::get<int>(result->data), 63);
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "double_it", staticDoubleIt));
+}
+
+// Test forwarding calls from a block with naab_rust_host_call
+TEST(RustFFITest, HostCallForwarding) {
+    using namespace naab;
+    using interpreter::Value;
+
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "double_it", staticDoubleIt));
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "forward", staticForward));
+    runtime::RustExecutor executor;
+
+    // Bare name: resolved in the calling block's library
+    auto bare = executor.executeBlock("rust://static_test::forward",
+        {std::make_shared<Value>(std::string("double_it")), std::make_shared<Value>(5)});
+    EXPECT_EQ(std::get<int>(bare->data), 15);
+
+    // Full URI
+    auto full = executor.executeBlock("rust://static_test::forward",
+        {std::make_shared<Value>(std::string("rust://static_test::double_it")), std::make_shared<Value>(7)});
+    EXPECT_EQ(std::get<int>(full->data), 21);
+
+    // A failing forwarded call fails the outer block with the inner reason
+    EXPECT_THROW(executor.executeBlock("rust://static_test::forward",
+        {std::make_shared<Value>(std::string("not a uri::x")), std::make_shared<Value>(1)}),
+        std::runtime_error);
+
+    // Outside any block call there is nothing to forward through
+    NaabRustValue* arg = naab_rust_value_create_int(1);
+    EXPECT_EQ(naab_rust_host_call("double_it", &arg, 1), nullptr);
+    NaabRustError* error = naab_rust_get_last_error();
+    ASSERT_NE(error, nullptr);
+    EXPECT_NE(std::strstr(error->message, "no Rust block call in progress"), nullptr);
+    naab_rust_error_free(error);
+    naab_rust_value_free(arg);
+}
+
+// Test load-time configuration from naab.toml reaching naab_rust_lib_init
+TEST(RustFFITest, LibraryConfigFromManifest) {
+    using namespace naab;
+    using interpreter::Value;
+    using List = std::vector<std::shared_ptr<Value>>;
+
+    const std::string lib = NAAB_RUST_TEST_LIBRARY;
+    auto dir = std::filesystem::temp_directory_path() / "naab_rust_config_test";
+    std::filesystem::create_directories(dir);
+    std::ofstream(dir / "naab.toml")
+        << "[package]\nname = \"config-test\"\nversion = \"0.1.0\"\n\n"
+        << "[rust.\"" << lib << "\"]\n"
+        << "endpoint = \"https://example.test\"\nretries = 3\nbig = 10000000000\nverbose = true\n";
+    auto loaded = manifest::ManifestLoader::load((dir / "naab.toml").string());
+    std::filesystem::remove_all(dir);
+    ASSERT_TRUE(loaded.has_value());
+
+    runtime::RustExecutor executor;
+    executor.configureLibraries(loaded->rust_libraries);
+    auto config = executor.executeBlock("rust://" + lib + "::config", {});
+
+    // [key, value] pairs sorted by key; the oversized int arrives as text
+    ASSERT_TRUE(std::holds_alternative<List>(config->data));
+    const auto& pairs = std::get<List>(config->data);
+    ASSERT_EQ(pairs.size(), 4u);
+    auto pair = [&](size_t i) { return std::get<List>(pairs[i]->data); };
+    EXPECT_EQ(std::get<std::string>(pair(0)[0]->data), "big");
+    EXPECT_EQ(std::get<std::string>(pair(0)[1]->data), "10000000000");
+    EXPECT_EQ(std::get<std::string>(pair(1)[1]->data), "https://example.test");
+    EXPECT_EQ(std::get<int>(pair(2)[1]->data), 3);
+    EXPECT_EQ(std::get<bool>(pair(3)[1]->data), true);
+}
+
+// Test that executors sharing a library run its init once
+TEST(RustFFITest, LibraryInitOncePerHandle) {
+    using namespace naab;
+    using interpreter::Value;
+
+    const std::string lib = NAAB_RUST_TEST_LIBRARY;
+    const std::string block = "rust://" + lib + "::config";
+    {
+        runtime::RustExecutor first;
+        first.setLibraryConfig(lib, std::make_shared<Value>(1));
+        EXPECT_EQ(std::get<int>(first.executeBlock(block, {})->data), 1);
+
+        runtime::RustExecutor second;
+        second.setLibraryConfig(lib, std::make_shared<Value>(2));  // Not loaded here yet
+        EXPECT_EQ(std::get<int>(second.executeBlock(block, {})->data), 1);
+    }
+
+    // Once the last executor has unloaded it, the next load initializes again
+    runtime::RustExecutor third;
+    third.setLibraryConfig(lib, std::make_shared<Value>(3));
+    EXPECT_EQ(std::get<int>(third.executeBlock(block, {})->data), 3);
+}
+
+// Test that a configuration that cannot be converted fails the load cleanly
+TEST(RustFFITest, LibraryConfigConversionFailure) {
+    using namespace naab;
+    using interpreter::Value;
+    using List = std::vector<std::shared_ptr<Value>>;
+
+    const std::string lib = NAAB_RUST_TEST_LIBRARY;
+    auto cyclic = std::make_shared<Value>(List{});
+    std::get<List>(cyclic->data).push_back(cyclic);
+
+    runtime::RustExecutor executor;
+    executor.setLibraryConfig(lib, cyclic);
+    EXPECT_THROW(executor.executeBlock("rust://" + lib + "::config", {}), std::runtime_error);
+    std::get<List>(cyclic->data).clear();
+
+    // Nothing was cached, so a fixed configuration loads normally
+    executor.setLibraryConfig(lib, std::make_shared<Value>(7));
+    auto config = executor.executeBlock("rust://" + lib + "::config", {});
+    EXPECT_EQ(std::get<int>(config->data), 7);
+}
+
+// Requests a config reload, then forwards to the block URI given as the argument
+static NaabRustValue* staticReloadForward(NaabRustValue** args, size_t arg_count) {
+    if (arg_count != 1) return nullptr;
+    naab::runtime::RustExecutor::requestConfigReload();
+    return naab_rust_host_call(naab_rust_value_get_string(args[0]), nullptr, 0);
+}
+
+// Test pushing new configuration to a loaded library
+TEST(RustFFITest, LibraryReconfigure) {
+    using namespace naab;
+    using interpreter::Value;
+
+    const std::string lib = NAAB_RUST_TEST_LIBRARY;
+    const std::string block = "rust://" + lib + "::config";
+    runtime::RustExecutor executor;
+    executor.setLibraryConfig(lib, std::make_shared<Value>(1));
+    EXPECT_EQ(std::get<int>(executor.executeBlock(block, {})->data), 1);
+
+    // Loaded: goes to naab_rust_lib_reconfigure without a reload
+    executor.setLibraryConfig(lib, std::make_shared<Value>(2));
+    EXPECT_EQ(std::get<int>(executor.executeBlock(block, {})->data), 2);
+
+    // Rejected: the library keeps its previous configuration
+    EXPECT_THROW(executor.setLibraryConfig(lib, std::make_shared<Value>(std::string("reject"))),
+                 std::runtime_error);
+    EXPECT_EQ(std::get<int>(executor.executeBlock(block, {})->data), 2);
+
+    // A requested reload is applied from the config source before the next call
+    manifest::RustLibraryConfig reloaded;
+    reloaded.settings.emplace_back("retries", int64_t{5});
+    executor.setConfigSource([&] {
+        return std::unordered_map<std::string, manifest::RustLibraryConfig>{{lib, reloaded}};
+    });
+    runtime::RustExecutor::requestConfigReload();
+    auto config = executor.executeBlock(block, {});
+    using List = std::vector<std::shared_ptr<Value>>;
+    ASSERT_TRUE(std::holds_alternative<List>(config->data));
+    const auto& pair = std::get<List>(std::get<List>(config->data).at(0)->data);
+    EXPECT_EQ(std::get<std::string>(pair.at(0)->data), "retries");
+    EXPECT_EQ(std::get<int>(pair.at(1)->data), 5);
+
+    // Not while a block of the library may be running: forwarded calls wait
+    auto setting = [](const std::shared_ptr<Value>& value) {
+        return std::get<int>(std::get<List>(std::get<List>(value->data).at(0)->data).at(1)->data);
+    };
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "reload_forward", staticReloadForward));
+    reloaded.settings[0].second = int64_t{6};
+    EXPECT_EQ(setting(executor.executeBlock("rust://static_test::reload_forward",
+                                            {std::make_shared<Value>(block)})), 5);
+    EXPECT_EQ(setting(executor.executeBlock(block, {})), 6);
+}
+
+// Test that a library's argument validator stops a call before the block runs
+TEST(RustFFITest, ValidatorRejectsCall) {
+    using namespace naab;
+    using interpreter::Value;
+
+    const std::string lib = NAAB_RUST_TEST_LIBRARY;
+    const std::string block = "rust://" + lib + "::count";
+    runtime::RustExecutor executor;
+    int runs = std::get<int>(executor.executeBlock(block, {std::make_shared<Value>(1)})->data);
+
+    // A FUNCTION argument holds a reference to the host value until freed
+    auto fn = std::make_shared<Value>(std::make_shared<interpreter::FunctionValue>(
+        "f", std::vector<std::string>{}, std::vector<ast::Type>{},
+        std::vector<ast::Expr*>{}, nullptr));
+    long refs = fn.use_count();
+
+    try {
+        executor.executeBlock(block, {fn, std::make_shared<Value>(-1)});
+        FAIL() << "validator did not reject the call";
+    } catch (const std::runtime_error& e) {
+        EXPECT_NE(std::string(e.what()).find("negative arguments are not allowed"), std::string::npos);
+    }
+    EXPECT_EQ(fn.use_count(), refs);  // Converted arguments were freed
+
+    // The block never ran for the rejected call
+    EXPECT_EQ(std::get<int>(executor.executeBlock(block, {})->data), runs + 1);
+}
+
+static NaabRustValue* staticFail(NaabRustValue** /*args*/, size_t /*arg_count*/) {
+    naab_rust_report_error("fail", "boom", "src/fail.rs", 12);
+    return nullptr;
+}
+
+// Test that a caught block error reports its Rust location without leaving a frame behind
+TEST(RustFFITest, BlockErrorLeavesTraceBalanced) {
+    using namespace naab;
+
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "fail", staticFail));
+    runtime::RustExecutor executor;
+    size_t depth = error::StackTracer::getTrace().size();
+
+    for (int i = 0; i < 2; ++i) {
+        try {
+            executor.executeBlock("rust://static_test::fail", {});
+            FAIL() << "block error was not raised";
+        } catch (const std::runtime_error& e) {
+            EXPECT_NE(std::string(e.what()).find("boom (src/fail.rs:12)"), std::string::npos);
+        }
+        EXPECT_EQ(error::StackTracer::getTrace().size(), depth);
+    }
+}
+
+// Reports what the active sandbox lets a block do: [getenv allowed, can write /tmp]
+static NaabRustValue* staticSandboxProbe(NaabRustValue** /*args*/, size_t /*arg_count*/) {
+    NaabRustValue* env = naab_rust_host_getenv("PATH");
+    bool env_allowed = env != nullptr;
+    naab_rust_value_free(env);
+
+    NaabRustValue* results[] = {
+        naab_rust_value_create_bool(env_allowed),
+        naab_rust_value_create_bool(naab_rust_sandbox_can_write("/tmp/naab_rust_probe")),
+    };
+    return naab_rust_value_create_list(results, 2);
+}
+
+// Test that block calls run under the configured default sandbox
+TEST(RustFFITest, ExecuteBlockUsesDefaultSandbox) {
+    using namespace naab;
+    using List = std::vector<std::shared_ptr<interpreter::Value>>;
+
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "sandbox_probe", staticSandboxProbe));
+    auto& manager = security::SandboxManager::instance();
+    security::SandboxConfig previous = manager.getDefaultConfig();
+    runtime::RustExecutor executor;
+
+    manager.setDefaultConfig(security::SandboxConfig::fromPermissionLevel(security::PermissionLevel::RESTRICTED));
+    auto restricted = std::get<List>(executor.executeBlock("rust://static_test::sandbox_probe", {})->data);
+    EXPECT_FALSE(std::get<bool>(restricted.at(0)->data));
+    EXPECT_FALSE(std::get<bool>(restricted.at(1)->data));
+
+    manager.setDefaultConfig(security::SandboxConfig::fromPermissionLevel(security::PermissionLevel::STANDARD));
+    auto standard = std::get<List>(executor.executeBlock("rust://static_test::sandbox_probe", {})->data);
+    EXPECT_TRUE(std::get<bool>(standard.at(0)->data));
+    EXPECT_TRUE(std::get<bool>(standard.at(1)->data));
+
+    manager.setDefaultConfig(previous);
+    EXPECT_EQ(security::ScopedSandbox::getCurrent(), nullptr);  // Scope ends with the call
+}
+
+// SIGINT disposition: 0 default, 1 ignored, 2 a handler
+static int sigintState() {
+    struct sigaction current;
+    sigaction(SIGINT, nullptr, &current);
+    if (current.sa_handler == SIG_DFL) return 0;
+    return current.sa_handler == SIG_IGN ? 1 : 2;
+}
+
+static NaabRustValue* staticSigintState(NaabRustValue** /*args*/, size_t /*arg_count*/) {
+    return naab_rust_value_create_int(sigintState());
+}
+
+// Runs until released, to overlap with a call on another thread
+static std::atomic<bool> hold_started{false};
+static std::atomic<bool> hold_release{false};
+static NaabRustValue* staticHold(NaabRustValue** /*args*/, size_t /*arg_count*/) {
+    hold_started = true;
+    while (!hold_release) {
+        std::this_thread::yield();
+    }
+    return naab_rust_value_create_int(sigintState());
+}
+
+// Test the SIGINT handler around block calls, including overlapping calls
+TEST(RustFFITest, InterruptHandlerScope) {
+    using namespace naab;
+
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "sigint_state", staticSigintState));
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "hold", staticHold));
+    ASSERT_EQ(sigintState(), 0);
+
+    // One call on another thread outlives one on this thread
+    std::thread worker([] {
+        runtime::RustExecutor executor;
+        auto state = executor.executeBlock("rust://static_test::hold", {});
+        EXPECT_EQ(std::get<int>(state->data), 2);
+    });
+    while (!hold_started) {
+        std::this_thread::yield();
+    }
+    runtime::RustExecutor executor;
+    EXPECT_EQ(std::get<int>(executor.executeBlock("rust://static_test::sigint_state", {})->data), 2);
+    EXPECT_EQ(sigintState(), 2);  // Still installed for the call still running
+    hold_release = true;
+    worker.join();
+    EXPECT_EQ(sigintState(), 0);  // Restored by the last call
+
+    // An inherited SIG_IGN stays in place during calls
+    signal(SIGINT, SIG_IGN);
+    EXPECT_EQ(std::get<int>(executor.executeBlock("rust://static_test::sigint_state", {})->data), 1);
+    EXPECT_EQ(sigintState(), 1);
+    signal(SIGINT, SIG_DFL);
+}
+
+// Block that gets Ctrl-C mid-call and returns normally
+static NaabRustValue* staticInterruptSelf(NaabRustValue** /*args*/, size_t /*arg_count*/) {
+    raise(SIGINT);
+    return naab_rust_value_create_bool(naab_rust_interrupted());
+}
+
+// Test that libraries are shut down before a re-raised SIGINT ends the process
+TEST(RustFFIDeathTest, InterruptShutsDownLibraries) {
+    using namespace naab;
+
+    ASSERT_TRUE(naab_rust_register_static_block("static_test", "interrupt_self", staticInterruptSelf));
+    const std::string lib = NAAB_RUST_TEST_LIBRARY;
+    EXPECT_EXIT({
+        runtime::RustExecutor executor;
+        executor.executeBlock("rust://" + lib + "::config", {});
+        executor.executeBlock("rust://static_test::interrupt_self", {});
+    }, testing::KilledBySignal(SIGINT), "\\[rust_test_library\\] shutdown");
+}
+
+#if defined(NAAB_RUST_CHECK_VALUES) || defined(NAAB_RUST_LEAK_CHECK)
+// Debug and leak-check builds abort on double free and use-after-free
+// instead of corrupting the heap
+TEST(RustFFIDeathTest, DoubleFreeAborts) {
+    EXPECT_DEATH({
+        NaabRustValue* val = naab_rust_value_create_int(7);
+        naab_rust_value_free(val);
+        naab_rust_value_free(val);
+    }, "not live");
+}
+
+TEST(RustFFIDeathTest, UseAfterFreeAborts) {
+    EXPECT_DEATH({
+        NaabRustValue* val = naab_rust_value_create_string("gone");
+        naab_rust_value_free(val);
+        naab_rust_value_get_string(val);
+    }, "get_string on value");
+}
+#endif
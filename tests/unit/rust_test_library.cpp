@@ -0,0 +1,62 @@
+// Stand-in Rust block library for rust_ffi_test
+// Built as a shared library and loaded with dlopen, so the executor's
+// library hooks run the way they do for a real Rust crate
+
+#include "naab/rust_ffi.h"
+#include <cstdio>
+#include <cstring>
+
+// Configuration received from naab_rust_lib_init
+static NaabRustValue* current_config = nullptr;
+
+extern "C" int naab_rust_lib_init(const NaabRustValue* config) {
+    naab_rust_value_free(current_config);  // Loaded again by a later test
+    current_config = naab_rust_value_clone(config);
+    return current_config ? 0 : 1;
+}
+
+// Takes any configuration except the string "reject"
+extern "C" int naab_rust_lib_reconfigure(const NaabRustValue* config) {
+    if (naab_rust_value_get_type(config) == NAAB_RUST_TYPE_STRING &&
+        std::strcmp(naab_rust_value_get_string(config), "reject") == 0) {
+        naab_rust_report_error(nullptr, "configuration rejected", __FILE__, __LINE__);
+        return 1;
+    }
+    NaabRustValue* copy = naab_rust_value_clone(config);
+    if (!copy) return 1;
+    naab_rust_value_free(current_config);
+    current_config = copy;
+    return 0;
+}
+
+extern "C" void naab_rust_lib_shutdown() {
+    std::fputs("[rust_test_library] shutdown\n", stderr);  // Seen by death tests
+    naab_rust_value_free(current_config);
+    current_config = nullptr;
+}
+
+// Block: copy of the configuration the library is running with
+extern "C" NaabRustValue* config(NaabRustValue** /*args*/, size_t /*arg_count*/) {
+    return naab_rust_value_clone(current_config);
+}
+
+// Rejects any call with a negative int argument
+extern "C" int naab_rust_validate_args(const char* block_name, NaabRustValue* const* args,
+                                       size_t arg_count) {
+    for (size_t i = 0; i < arg_count; ++i) {
+        if (naab_rust_value_get_type(args[i]) == NAAB_RUST_TYPE_INT &&
+            naab_rust_value_get_int(args[i]) < 0) {
+            naab_rust_report_error(block_name, "negative arguments are not allowed", __FILE__, __LINE__);
+            return 1;
+        }
+    }
+    return 0;
+}
+
+// Times the "count" block actually ran
+static int count_calls = 0;
+
+// Block: how many times it has run, including this call
+extern "C" NaabRustValue* count(NaabRustValue** /*args*/, size_t /*arg_count*/) {
+    return naab_rust_value_create_int(++count_calls);
+}